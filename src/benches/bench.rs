@@ -1,12 +1,14 @@
+use crate::lasso::memory_checking::MemoryCheckingProof;
 use crate::lasso::surge::SparsePolyCommitmentGens;
-use crate::subtables::and::AndSubtableStrategy;
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::subtables::{and::AndSubtableStrategy, SubtableStrategy, Subtables};
 use crate::{
   lasso::{densified::DensifiedRepresentation, surge::SparsePolynomialEvaluationProof},
   utils::random::RandomTape,
 };
 use ark_curve25519::{EdwardsProjective, Fr};
 use ark_ff::PrimeField;
-use ark_std::{log2, test_rng};
+use ark_std::{log2, test_rng, UniformRand};
 use merlin::Transcript;
 use rand_chacha::rand_core::RngCore;
 
@@ -59,11 +61,13 @@ macro_rules! single_pass_lasso {
       let mut prover_transcript = Transcript::new(b"example");
       let proof = SparsePolynomialEvaluationProof::<G, C, M, SubtableStrategy>::prove(
         &mut dense,
+        &commitment,
         &r,
         &gens,
         &mut prover_transcript,
         &mut random_tape,
-      );
+      )
+      .expect("should prove");
       let mut verify_transcript = Transcript::new(b"example");
       proof
         .verify(&commitment, &r, &gens, &mut verify_transcript)
@@ -72,10 +76,73 @@ macro_rules! single_pass_lasso {
   };
 }
 
+macro_rules! memory_checking {
+  ($span_name:expr, $field:ty, $group:ty, $subtable_strategy:ty, $C:expr, $M:expr, $sparsity:expr) => {
+    (tracing::info_span!($span_name), move || {
+      const C: usize = $C;
+      const M: usize = $M;
+      const S: usize = $sparsity;
+      type F = $field;
+      type G = $group;
+      type Strategy = $subtable_strategy;
+      const NUM_MEMORIES: usize = <Strategy as SubtableStrategy<F, C, M>>::NUM_MEMORIES;
+
+      let log_m = log2(M) as usize;
+
+      let nz = gen_indices::<C>(S, M);
+      let dense: DensifiedRepresentation<F, C> =
+        DensifiedRepresentation::from_lookup_indices(&nz, log_m);
+      let gens = SparsePolyCommitmentGens::<G>::new(b"gens_sparse_poly", C, S, NUM_MEMORIES, log_m);
+      let commitment = dense.commit::<G>(&gens);
+      let subtables = Subtables::<F, C, M, Strategy>::new(&dense.dim_usize, dense.s);
+      let comm_derefs = subtables.commit(&gens.gens_derefs);
+      let r_mem_check = gen_random_point::<F>(2);
+      let r_mem_check = (r_mem_check[0], r_mem_check[1]);
+
+      // Prove
+      let mut random_tape = RandomTape::new(b"proof");
+      let mut prover_transcript = Transcript::new(b"example");
+      let start = std::time::Instant::now();
+      let proof = MemoryCheckingProof::<G, C, M, Strategy>::prove(
+        &dense,
+        &r_mem_check,
+        &subtables,
+        &gens,
+        &mut prover_transcript,
+        &mut random_tape,
+      )
+      .expect("should prove");
+      let lookups_per_sec = S as f64 / start.elapsed().as_secs_f64();
+      tracing::info!(lookups_per_sec, "Memory checking prover throughput");
+
+      let mut verify_transcript = Transcript::new(b"example");
+      proof
+        .verify(
+          &commitment,
+          &comm_derefs,
+          &gens,
+          &r_mem_check,
+          S,
+          &mut verify_transcript,
+        )
+        .expect("should verify");
+    })
+  };
+}
+
 #[derive(Debug, Clone, clap::ValueEnum)]
 pub enum BenchType {
   JoltDemo,
   Halo2Comparison,
+  MemoryChecking,
+  EvaluateMany,
+  MsmWindow,
+  BatchMsm,
+  MsmSmall,
+  MsmNesting,
+  FromU64Evals,
+  BatchInvert,
+  SubtablePreprocessing,
 }
 
 #[allow(unreachable_patterns)] // good errors on new BenchTypes
@@ -83,6 +150,19 @@ pub fn benchmarks(bench_type: BenchType) -> Vec<(tracing::Span, fn())> {
   match bench_type {
     BenchType::JoltDemo => jolt_demo_benchmarks(),
     BenchType::Halo2Comparison => halo2_comparison_benchmarks(),
+    BenchType::MemoryChecking => memory_checking_benchmarks(),
+    BenchType::EvaluateMany => evaluate_many_benchmarks(),
+    #[cfg(not(feature = "ark-msm"))]
+    BenchType::MsmWindow => msm_window_benchmarks(),
+    #[cfg(not(feature = "ark-msm"))]
+    BenchType::BatchMsm => batch_msm_benchmarks(),
+    #[cfg(not(feature = "ark-msm"))]
+    BenchType::MsmSmall => msm_small_benchmarks(),
+    #[cfg(all(feature = "multicore", not(feature = "ark-msm")))]
+    BenchType::MsmNesting => msm_nesting_benchmarks(),
+    BenchType::FromU64Evals => from_u64_evals_benchmarks(),
+    BenchType::BatchInvert => batch_invert_benchmarks(),
+    BenchType::SubtablePreprocessing => subtable_preprocessing_benchmarks(),
     _ => panic!("BenchType does not have a mapping"),
   }
 }
@@ -231,3 +311,345 @@ fn halo2_comparison_benchmarks() -> Vec<(tracing::Span, fn())> {
     ),
   ]
 }
+
+fn memory_checking_benchmarks() -> Vec<(tracing::Span, fn())> {
+  vec![
+    memory_checking!(
+      "MemoryChecking(2^10)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 4,
+      /* M= */ 1 << 16,
+      /* S= */ 1 << 10
+    ),
+    memory_checking!(
+      "MemoryChecking(2^12)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 4,
+      /* M= */ 1 << 16,
+      /* S= */ 1 << 12
+    ),
+    memory_checking!(
+      "MemoryChecking(2^14)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 4,
+      /* M= */ 1 << 16,
+      /* S= */ 1 << 14
+    ),
+    memory_checking!(
+      "MemoryChecking(2^16)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 4,
+      /* M= */ 1 << 16,
+      /* S= */ 1 << 16
+    ),
+    memory_checking!(
+      "MemoryChecking(2^18)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 4,
+      /* M= */ 1 << 16,
+      /* S= */ 1 << 18
+    ),
+    memory_checking!(
+      "MemoryChecking(2^20)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 4,
+      /* M= */ 1 << 16,
+      /* S= */ 1 << 20
+    ),
+  ]
+}
+
+/// `evaluate_many` against one `evaluate` per point, for 8 points on a 2^16 polynomial. Points are
+/// either independent or share all but their last 4 coordinates.
+fn evaluate_many_benchmarks() -> Vec<(tracing::Span, fn())> {
+  fn run(shared_prefix: usize) {
+    const NUM_VARS: usize = 16;
+    let mut rng = test_rng();
+    let poly = DensePolynomial::new((0..1 << NUM_VARS).map(|_| Fr::rand(&mut rng)).collect());
+    let prefix: Vec<Fr> = (0..shared_prefix).map(|_| Fr::rand(&mut rng)).collect();
+    let points: Vec<Vec<Fr>> = (0..8)
+      .map(|_| {
+        let mut point = prefix.clone();
+        point.extend((shared_prefix..NUM_VARS).map(|_| Fr::rand(&mut rng)));
+        point
+      })
+      .collect();
+
+    let naive: Vec<Fr> = tracing::info_span!("evaluate")
+      .in_scope(|| points.iter().map(|r| poly.evaluate(r)).collect());
+    let many = tracing::info_span!("evaluate_many").in_scope(|| poly.evaluate_many(&points));
+    assert_eq!(naive, many);
+  }
+
+  vec![
+    (
+      tracing::info_span!("EvaluateMany(2^16, 8 independent)"),
+      || run(0),
+    ),
+    (
+      tracing::info_span!("EvaluateMany(2^16, 8 sharing 12 vars)"),
+      || run(12),
+    ),
+  ]
+}
+
+/// `num_bases` random affine bases and full-width random scalars.
+#[cfg(not(feature = "ark-msm"))]
+fn gen_msm_inputs(num_bases: usize) -> (Vec<ark_curve25519::EdwardsAffine>, Vec<Fr>) {
+  use ark_ec::CurveGroup;
+
+  let mut rng = test_rng();
+  let bases: Vec<EdwardsProjective> = (0..num_bases)
+    .map(|_| EdwardsProjective::rand(&mut rng))
+    .collect();
+  let scalars = (0..num_bases).map(|_| Fr::rand(&mut rng)).collect();
+  (EdwardsProjective::normalize_batch(&bases), scalars)
+}
+
+/// MSMs with fixed window sizes against `optimal_window_size`, at 2^10, 2^14 and 2^18 bases.
+#[cfg(not(feature = "ark-msm"))]
+fn msm_window_benchmarks() -> Vec<(tracing::Span, fn())> {
+  use crate::msm::{msm_with_window, optimal_window_size};
+
+  fn run(log_n: usize) {
+    let (bases, scalars) = gen_msm_inputs(1 << log_n);
+    let tuned = optimal_window_size(bases.len());
+    let expected: EdwardsProjective = tracing::info_span!("msm", window = tuned, tuned = true)
+      .in_scope(|| msm_with_window(&bases, &scalars, tuned));
+    for c in [4, 8, 12, 16] {
+      let result: EdwardsProjective = tracing::info_span!("msm", window = c, tuned = false)
+        .in_scope(|| msm_with_window(&bases, &scalars, c));
+      assert_eq!(result, expected);
+    }
+  }
+
+  vec![
+    (tracing::info_span!("MsmWindow(2^10)"), || run(10)),
+    (tracing::info_span!("MsmWindow(2^14)"), || run(14)),
+    (tracing::info_span!("MsmWindow(2^18)"), || run(18)),
+  ]
+}
+
+/// `batch_msm` against one `msm` per scalar set, committing 32 polynomials over shared bases.
+#[cfg(not(feature = "ark-msm"))]
+fn batch_msm_benchmarks() -> Vec<(tracing::Span, fn())> {
+  use crate::msm::VariableBaseMSM;
+
+  fn run(log_n: usize) {
+    let (bases, _) = gen_msm_inputs(1 << log_n);
+    let mut rng = test_rng();
+    let scalar_sets: Vec<Vec<Fr>> = (0..32)
+      .map(|_| (0..bases.len()).map(|_| Fr::rand(&mut rng)).collect())
+      .collect();
+    let scalar_sets: Vec<&[Fr]> = scalar_sets.iter().map(Vec::as_slice).collect();
+
+    let individual: Vec<EdwardsProjective> = tracing::info_span!("msm x32").in_scope(|| {
+      scalar_sets
+        .iter()
+        .map(|scalars| EdwardsProjective::msm(&bases, scalars).unwrap())
+        .collect()
+    });
+    let batched: Vec<EdwardsProjective> = tracing::info_span!("batch_msm")
+      .in_scope(|| EdwardsProjective::batch_msm(&bases, &scalar_sets));
+    assert_eq!(individual, batched);
+  }
+
+  vec![
+    (tracing::info_span!("BatchMsm(32 x 2^10)"), || run(10)),
+    (tracing::info_span!("BatchMsm(32 x 2^14)"), || run(14)),
+  ]
+}
+
+/// `msm_small` with a 16-bit bound against `msm`, over 16-bit scalars such as timestamps.
+#[cfg(not(feature = "ark-msm"))]
+fn msm_small_benchmarks() -> Vec<(tracing::Span, fn())> {
+  use crate::msm::VariableBaseMSM;
+
+  fn run(log_n: usize) {
+    let (bases, _) = gen_msm_inputs(1 << log_n);
+    let mut rng = test_rng();
+    let scalars: Vec<Fr> = (0..bases.len())
+      .map(|_| Fr::from(rng.next_u64() % (1 << 16)))
+      .collect();
+
+    let full: EdwardsProjective =
+      tracing::info_span!("msm").in_scope(|| EdwardsProjective::msm(&bases, &scalars).unwrap());
+    let small: EdwardsProjective = tracing::info_span!("msm_small")
+      .in_scope(|| EdwardsProjective::msm_small(&bases, &scalars, 16));
+    assert_eq!(full, small);
+  }
+
+  vec![
+    (tracing::info_span!("MsmSmall(2^14, 16 bits)"), || run(14)),
+    (tracing::info_span!("MsmSmall(2^18, 16 bits)"), || run(18)),
+  ]
+}
+
+/// One MSM per memory, as in per-memory grand products: MSMs one after another on the global pool
+/// (flat), inside an outer `par_iter` (nested), and inside an outer `par_iter` with each MSM
+/// limited to `msm_serial` or a share of the threads.
+#[cfg(all(feature = "multicore", not(feature = "ark-msm")))]
+fn msm_nesting_benchmarks() -> Vec<(tracing::Span, fn())> {
+  use crate::msm::VariableBaseMSM;
+  use rayon::prelude::*;
+
+  fn run(log_n: usize) {
+    const NUM_MSMS: usize = 8;
+    let (bases, _) = gen_msm_inputs(1 << log_n);
+    let mut rng = test_rng();
+    let scalar_sets: Vec<Vec<Fr>> = (0..NUM_MSMS)
+      .map(|_| (0..bases.len()).map(|_| Fr::rand(&mut rng)).collect())
+      .collect();
+    let threads_per_msm = (rayon::current_num_threads() / NUM_MSMS).max(1);
+
+    let flat: Vec<EdwardsProjective> = tracing::info_span!("flat").in_scope(|| {
+      scalar_sets
+        .iter()
+        .map(|scalars| EdwardsProjective::msm(&bases, scalars).unwrap())
+        .collect()
+    });
+    let nested: Vec<EdwardsProjective> = tracing::info_span!("nested").in_scope(|| {
+      scalar_sets
+        .par_iter()
+        .map(|scalars| EdwardsProjective::msm(&bases, scalars).unwrap())
+        .collect()
+    });
+    let nested_serial: Vec<EdwardsProjective> =
+      tracing::info_span!("nested_serial").in_scope(|| {
+        scalar_sets
+          .par_iter()
+          .map(|scalars| EdwardsProjective::msm_serial(&bases, scalars))
+          .collect()
+      });
+    let nested_capped: Vec<EdwardsProjective> =
+      tracing::info_span!("nested_capped", threads_per_msm).in_scope(|| {
+        scalar_sets
+          .par_iter()
+          .map(|scalars| EdwardsProjective::msm_with_threads(&bases, scalars, threads_per_msm))
+          .collect()
+      });
+    assert_eq!(flat, nested);
+    assert_eq!(flat, nested_serial);
+    assert_eq!(flat, nested_capped);
+  }
+
+  vec![
+    (tracing::info_span!("MsmNesting(8 x 2^14)"), || run(14)),
+    (tracing::info_span!("MsmNesting(8 x 2^16)"), || run(16)),
+  ]
+}
+
+/// `DensePolynomial::from_u64_evals` against element-wise `Fr::from` on 2^20 evaluations.
+fn from_u64_evals_benchmarks() -> Vec<(tracing::Span, fn())> {
+  vec![(tracing::info_span!("FromU64Evals(2^20)"), || {
+    let mut rng = test_rng();
+    let evals: Vec<u64> = (0..1 << 20).map(|_| rng.next_u64()).collect();
+
+    let elementwise = tracing::info_span!("elementwise")
+      .in_scope(|| DensePolynomial::new(evals.iter().map(|z| Fr::from(*z)).collect()));
+    let converted = tracing::info_span!("from_u64_evals")
+      .in_scope(|| DensePolynomial::<Fr>::from_u64_evals(&evals));
+    assert!((0..evals.len()).all(|i| elementwise[i] == converted[i]));
+  })]
+}
+
+/// `batch_invert` against one `inverse` per element, on 4096 elements.
+fn batch_invert_benchmarks() -> Vec<(tracing::Span, fn())> {
+  use crate::utils::batch_invert;
+  use ark_ff::Field;
+
+  vec![(tracing::info_span!("BatchInvert(4096)"), || {
+    let mut rng = test_rng();
+    let elements: Vec<Fr> = (0..4096).map(|_| Fr::rand(&mut rng)).collect();
+
+    let individual: Vec<Fr> = tracing::info_span!("inverse")
+      .in_scope(|| elements.iter().map(|x| x.inverse().unwrap()).collect());
+    let mut batched = elements.clone();
+    tracing::info_span!("batch_invert").in_scope(|| batch_invert(&mut batched));
+    assert_eq!(individual, batched);
+  })]
+}
+
+/// Repeated proving with subtables materialized for every proof against materialized once up front.
+fn subtable_preprocessing_benchmarks() -> Vec<(tracing::Span, fn())> {
+  use crate::subtables::SubtablePreprocessing;
+
+  vec![(
+    tracing::info_span!("SubtablePreprocessing(8 x And(2^64, 2^10))"),
+    || {
+      const C: usize = 4;
+      const M: usize = 1 << 16;
+      const S: usize = 1 << 10;
+      const NUM_PROOFS: usize = 8;
+      type G = EdwardsProjective;
+      let log_m = log2(M) as usize;
+
+      let nz = gen_indices::<C>(S, M);
+      let mut dense: DensifiedRepresentation<Fr, C> =
+        DensifiedRepresentation::from_lookup_indices(&nz, log_m);
+      let gens = SparsePolyCommitmentGens::<G>::new(b"gens_sparse_poly", C, S, C, log_m);
+      let commitment = dense.commit::<G>(&gens);
+      let r: Vec<Fr> = gen_random_point::<Fr>(log2(S) as usize);
+
+      tracing::info_span!("fresh").in_scope(|| {
+        for _ in 0..NUM_PROOFS {
+          SparsePolynomialEvaluationProof::<G, C, M, AndSubtableStrategy>::prove(
+            &mut dense,
+            &commitment,
+            &r,
+            &gens,
+            &mut Transcript::new(b"example"),
+            &mut RandomTape::new(b"proof"),
+          )
+          .expect("should prove");
+        }
+      });
+      tracing::info_span!("preprocessed").in_scope(|| {
+        let preprocessing = SubtablePreprocessing::<Fr, C, M, AndSubtableStrategy>::new();
+        for _ in 0..NUM_PROOFS {
+          SparsePolynomialEvaluationProof::<G, C, M, AndSubtableStrategy>::prove_preprocessed(
+            &mut dense,
+            &commitment,
+            &preprocessing,
+            &r,
+            &gens,
+            &mut Transcript::new(b"example"),
+            &mut RandomTape::new(b"proof"),
+          )
+          .expect("should prove");
+        }
+      });
+    },
+  )]
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn memory_checking_smoke() {
+    let (span, bench) = memory_checking!(
+      "MemoryChecking(2^10, M = 2^10)",
+      Fr,
+      EdwardsProjective,
+      AndSubtableStrategy,
+      /* C= */ 2,
+      /* M= */ 1 << 10,
+      /* S= */ 1 << 10
+    );
+    span.in_scope(bench);
+  }
+}
@@ -1,5 +1,8 @@
 use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::test_rng;
 use merlin::Transcript;
+use rand_chacha::rand_core::RngCore;
 
 use crate::{
   lasso::{
@@ -7,8 +10,15 @@ use crate::{
     surge::{SparsePolyCommitmentGens, SparsePolynomialEvaluationProof},
   },
   subtables::{
-    and::AndSubtableStrategy, lt::LTSubtableStrategy, range_check::RangeCheckSubtableStrategy,
-    SubtableStrategy,
+    and::AndSubtableStrategy,
+    branch::{BEQSubtableStrategy, BGESubtableStrategy},
+    eq::EqSubtableStrategy,
+    lt::LTSubtableStrategy,
+    or::OrSubtableStrategy,
+    range_check::RangeCheckSubtableStrategy,
+    slt::SLTSubtableStrategy,
+    xor::XorSubtableStrategy,
+    SubtablePreprocessing, SubtableStrategy, Subtables,
   },
   utils::math::Math,
   utils::random::RandomTape,
@@ -44,11 +54,88 @@ macro_rules! e2e_test {
       let mut prover_transcript = Transcript::new(b"example");
       let proof = SparsePolynomialEvaluationProof::<$G, C, $M, $Strategy>::prove(
         &mut dense,
+        &commitment,
         &r,
         &gens,
         &mut prover_transcript,
         &mut random_tape,
+      )
+      .unwrap();
+
+      let mut verifier_transcript = Transcript::new(b"example");
+      assert!(
+        proof
+          .verify(&commitment, &r, &gens, &mut verifier_transcript)
+          .is_ok(),
+        "Failed to verify proof."
+      );
+    }
+  };
+}
+
+/// Like `e2e_test!`, but derives lookup indices from random `WORD_SIZE`-bit operands and
+/// checks the combined lookups against the native bitwise operation before proving.
+macro_rules! e2e_bitwise_test {
+  ($test_name:ident, $Strategy:ty, $op:expr, $C:expr, $M:expr, $WORD_SIZE:expr, $sparsity:expr) => {
+    #[test]
+    fn $test_name() {
+      use crate::utils::test::{chunk_operands, gen_random_point};
+      use ark_std::log2;
+
+      const C: usize = $C;
+      const M: usize = $M;
+
+      // parameters
+      const NUM_MEMORIES: usize = <$Strategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+      let log_M: usize = M.log_2();
+      let log_s: usize = log2($sparsity) as usize;
+      assert_eq!(C * log_M / 2, $WORD_SIZE);
+
+      let mut rng = test_rng();
+      let word_mask = u64::MAX >> (64 - $WORD_SIZE);
+      let operands: Vec<(u64, u64)> = (0..$sparsity)
+        .map(|_| (rng.next_u64() & word_mask, rng.next_u64() & word_mask))
+        .collect();
+      let nz: Vec<[usize; C]> = operands
+        .iter()
+        .map(|(x, y)| chunk_operands(*x, *y, log_M))
+        .collect();
+
+      let mut dense: DensifiedRepresentation<Fr, C> =
+        DensifiedRepresentation::from_lookup_indices(&nz, log_M);
+
+      let subtables = Subtables::<Fr, C, M, $Strategy>::new(&dense.dim_usize, dense.s);
+      for (j, (x, y)) in operands.iter().enumerate() {
+        let vals: [Fr; NUM_MEMORIES] = std::array::from_fn(|i| subtables.lookup_polys[i][j]);
+        assert_eq!(
+          <$Strategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&vals),
+          Fr::from($op(*x, *y)),
+          "Lookup for operands ({x}, {y}) did not match native operation."
+        );
+      }
+
+      let gens = SparsePolyCommitmentGens::<G1Projective>::new(
+        b"gens_sparse_poly",
+        C,
+        $sparsity,
+        NUM_MEMORIES,
+        log_M,
       );
+      let commitment = dense.commit::<G1Projective>(&gens);
+
+      let r: Vec<Fr> = gen_random_point(log_s);
+
+      let mut random_tape = RandomTape::new(b"proof");
+      let mut prover_transcript = Transcript::new(b"example");
+      let proof = SparsePolynomialEvaluationProof::<G1Projective, C, M, $Strategy>::prove(
+        &mut dense,
+        &commitment,
+        &r,
+        &gens,
+        &mut prover_transcript,
+        &mut random_tape,
+      )
+      .unwrap();
 
       let mut verifier_transcript = Transcript::new(b"example");
       assert!(
@@ -79,6 +166,42 @@ e2e_test!(
   /* M= */ 16,
   /* sparsity= */ 128
 );
+e2e_test!(
+  prove_4d_slt,
+  SLTSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 16
+);
+e2e_test!(
+  prove_4d_eq,
+  EqSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 16
+);
+e2e_test!(
+  prove_4d_beq,
+  BEQSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 16
+);
+e2e_test!(
+  prove_4d_bge,
+  BGESubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 16
+);
 e2e_test!(
   prove_4d_and,
   AndSubtableStrategy,
@@ -97,3 +220,323 @@ e2e_test!(
   /* M= */ 256,
   /* sparsity= */ 16
 );
+
+e2e_bitwise_test!(
+  prove_and_32,
+  AndSubtableStrategy,
+  |x: u64, y: u64| x & y,
+  /* C= */ 4,
+  /* M= */ 1 << 16,
+  /* WORD_SIZE= */ 32,
+  /* sparsity= */ 16
+);
+e2e_bitwise_test!(
+  prove_and_64,
+  AndSubtableStrategy,
+  |x: u64, y: u64| x & y,
+  /* C= */ 8,
+  /* M= */ 1 << 16,
+  /* WORD_SIZE= */ 64,
+  /* sparsity= */ 16
+);
+e2e_bitwise_test!(
+  prove_or_32,
+  OrSubtableStrategy,
+  |x: u64, y: u64| x | y,
+  /* C= */ 4,
+  /* M= */ 1 << 16,
+  /* WORD_SIZE= */ 32,
+  /* sparsity= */ 16
+);
+e2e_bitwise_test!(
+  prove_or_64,
+  OrSubtableStrategy,
+  |x: u64, y: u64| x | y,
+  /* C= */ 8,
+  /* M= */ 1 << 16,
+  /* WORD_SIZE= */ 64,
+  /* sparsity= */ 16
+);
+e2e_bitwise_test!(
+  prove_xor_32,
+  XorSubtableStrategy,
+  |x: u64, y: u64| x ^ y,
+  /* C= */ 4,
+  /* M= */ 1 << 16,
+  /* WORD_SIZE= */ 32,
+  /* sparsity= */ 16
+);
+e2e_bitwise_test!(
+  prove_xor_64,
+  XorSubtableStrategy,
+  |x: u64, y: u64| x ^ y,
+  /* C= */ 8,
+  /* M= */ 1 << 16,
+  /* WORD_SIZE= */ 64,
+  /* sparsity= */ 16
+);
+
+#[test]
+fn serialized_proof_round_trip() {
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  const C: usize = 4;
+  const M: usize = 16;
+  const S: usize = 16;
+  const NUM_MEMORIES: usize = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  let log_M: usize = M.log_2();
+
+  let nz: Vec<[usize; C]> = gen_indices(S, M);
+  let mut dense: DensifiedRepresentation<Fr, C> =
+    DensifiedRepresentation::from_lookup_indices(&nz, log_M);
+  let gens =
+    SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, NUM_MEMORIES, log_M);
+  let commitment = dense.commit::<G1Projective>(&gens);
+  let r: Vec<Fr> = gen_random_point(S.log_2());
+
+  let mut random_tape = RandomTape::new(b"proof");
+  let mut prover_transcript = Transcript::new(b"example");
+  let proof = SparsePolynomialEvaluationProof::<G1Projective, C, M, LTSubtableStrategy>::prove(
+    &mut dense,
+    &commitment,
+    &r,
+    &gens,
+    &mut prover_transcript,
+    &mut random_tape,
+  )
+  .unwrap();
+
+  let mut proof_bytes = Vec::new();
+  proof.serialize_compressed(&mut proof_bytes).unwrap();
+  assert_eq!(proof_bytes.len(), proof.compressed_size());
+
+  let deserialized =
+    SparsePolynomialEvaluationProof::<G1Projective, C, M, LTSubtableStrategy>::deserialize_compressed(
+      &proof_bytes[..],
+    )
+    .unwrap();
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(
+    deserialized
+      .verify(&commitment, &r, &gens, &mut verifier_transcript)
+      .is_ok(),
+    "Failed to verify deserialized proof."
+  );
+}
+
+#[test]
+fn deterministic_tape_proofs_identical() {
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  const C: usize = 4;
+  const M: usize = 16;
+  const S: usize = 16;
+  const NUM_MEMORIES: usize = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  let log_M: usize = M.log_2();
+
+  let nz: Vec<[usize; C]> = gen_indices(S, M);
+  let gens =
+    SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, NUM_MEMORIES, log_M);
+  let r: Vec<Fr> = gen_random_point(S.log_2());
+
+  let prove = |seed: u64| {
+    let mut dense: DensifiedRepresentation<Fr, C> =
+      DensifiedRepresentation::from_lookup_indices(&nz, log_M);
+    let commitment = dense.commit::<G1Projective>(&gens);
+    let mut random_tape = RandomTape::new_deterministic(seed);
+    let mut prover_transcript = Transcript::new(b"example");
+    let proof = SparsePolynomialEvaluationProof::<G1Projective, C, M, LTSubtableStrategy>::prove(
+      &mut dense,
+      &commitment,
+      &r,
+      &gens,
+      &mut prover_transcript,
+      &mut random_tape,
+    )
+    .unwrap();
+    let mut proof_bytes = Vec::new();
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+    proof_bytes
+  };
+
+  assert_eq!(prove(7), prove(7));
+}
+
+#[test]
+fn preprocessed_subtables_match_fresh() {
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  const C: usize = 4;
+  const M: usize = 16;
+  const S: usize = 16;
+  const NUM_MEMORIES: usize = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  type Proof = SparsePolynomialEvaluationProof<G1Projective, C, M, LTSubtableStrategy>;
+  let log_M: usize = M.log_2();
+
+  let gens =
+    SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, NUM_MEMORIES, log_M);
+  let r: Vec<Fr> = gen_random_point(S.log_2());
+  let preprocessing = SubtablePreprocessing::<Fr, C, M, LTSubtableStrategy>::new();
+
+  let prove = |nz: &Vec<[usize; C]>, preprocessing: Option<&SubtablePreprocessing<_, C, M, _>>| {
+    let mut dense: DensifiedRepresentation<Fr, C> =
+      DensifiedRepresentation::from_lookup_indices(nz, log_M);
+    let commitment = dense.commit::<G1Projective>(&gens);
+    let mut random_tape = RandomTape::new_deterministic(7);
+    let mut prover_transcript = Transcript::new(b"example");
+    let proof = match preprocessing {
+      Some(preprocessing) => Proof::prove_preprocessed(
+        &mut dense,
+        &commitment,
+        preprocessing,
+        &r,
+        &gens,
+        &mut prover_transcript,
+        &mut random_tape,
+      ),
+      None => Proof::prove(
+        &mut dense,
+        &commitment,
+        &r,
+        &gens,
+        &mut prover_transcript,
+        &mut random_tape,
+      ),
+    }
+    .unwrap();
+    let mut proof_bytes = Vec::new();
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+    proof_bytes
+  };
+
+  // The same preprocessing is reused across proofs of different lookups
+  let nz = gen_indices::<C>(S, M);
+  let other_nz: Vec<[usize; C]> = (0..S).map(|i| [i % M; C]).collect();
+  assert_eq!(prove(&nz, Some(&preprocessing)), prove(&nz, None));
+  assert_eq!(
+    prove(&other_nz, Some(&preprocessing)),
+    prove(&other_nz, None)
+  );
+}
+
+#[test]
+fn proof_bound_to_commitment() {
+  use crate::lasso::surge::SparsePolynomialCommitment;
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  const C: usize = 4;
+  const M: usize = 16;
+  const S: usize = 16;
+  const NUM_MEMORIES: usize = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  let log_M: usize = M.log_2();
+
+  let gens =
+    SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, NUM_MEMORIES, log_M);
+  let mut dense: DensifiedRepresentation<Fr, C> =
+    DensifiedRepresentation::from_lookup_indices(&gen_indices(S, M), log_M);
+  let commitment = dense.commit::<G1Projective>(&gens);
+
+  // A commitment to different lookups
+  let other_indices: Vec<[usize; C]> = (0..S).map(|i| [i % M; C]).collect();
+  let other_commitment =
+    DensifiedRepresentation::<Fr, C>::from_lookup_indices(&other_indices, log_M)
+      .commit::<G1Projective>(&gens);
+
+  let r: Vec<Fr> = gen_random_point(S.log_2());
+  // Proves openings of `dense`, deriving the challenges from `prover_commitment`, and verifies them
+  // against `verifier_commitment`.
+  let mut prove_verify =
+    |prover_commitment: &SparsePolynomialCommitment<G1Projective>,
+     verifier_commitment: &SparsePolynomialCommitment<G1Projective>| {
+      let mut random_tape = RandomTape::new(b"proof");
+      let mut prover_transcript = Transcript::new(b"example");
+      let proof = SparsePolynomialEvaluationProof::<G1Projective, C, M, LTSubtableStrategy>::prove(
+        &mut dense,
+        prover_commitment,
+        &r,
+        &gens,
+        &mut prover_transcript,
+        &mut random_tape,
+      )
+      .unwrap();
+
+      let mut verifier_transcript = Transcript::new(b"example");
+      proof.verify(verifier_commitment, &r, &gens, &mut verifier_transcript)
+    };
+
+  assert!(prove_verify(&commitment, &commitment).is_ok());
+  // The prover absorbs the wrong commitment
+  assert!(prove_verify(&other_commitment, &commitment).is_err());
+  // The verifier checks against the wrong commitment
+  assert!(prove_verify(&commitment, &other_commitment).is_err());
+}
+
+#[test]
+fn malformed_proofs_rejected_without_panic() {
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  const C: usize = 4;
+  const M: usize = 16;
+  const S: usize = 16;
+  const NUM_MEMORIES: usize = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  type Proof = SparsePolynomialEvaluationProof<G1Projective, C, M, LTSubtableStrategy>;
+  let log_M: usize = M.log_2();
+
+  let gens =
+    SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, NUM_MEMORIES, log_M);
+  let mut dense: DensifiedRepresentation<Fr, C> =
+    DensifiedRepresentation::from_lookup_indices(&gen_indices(S, M), log_M);
+  let commitment = dense.commit::<G1Projective>(&gens);
+  let r: Vec<Fr> = gen_random_point(S.log_2());
+
+  let mut random_tape = RandomTape::new(b"proof");
+  let mut prover_transcript = Transcript::new(b"example");
+  let proof = Proof::prove(
+    &mut dense,
+    &commitment,
+    &r,
+    &gens,
+    &mut prover_transcript,
+    &mut random_tape,
+  )
+  .unwrap();
+  let mut proof_bytes = Vec::new();
+  proof.serialize_compressed(&mut proof_bytes).unwrap();
+
+  // Returns true if the bytes deserialized into a proof, which must then fail to verify.
+  let check = |bytes: &[u8]| -> bool {
+    // ark-serialize unwraps while decoding fixed-size arrays, so a panic during deserialization
+    // is treated as a decoding error; only the verifier itself must not panic.
+    let Ok(Ok(proof)) = std::panic::catch_unwind(|| Proof::deserialize_compressed(bytes)) else {
+      return false;
+    };
+    let mut verifier_transcript = Transcript::new(b"example");
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+      proof.verify(&commitment, &r, &gens, &mut verifier_transcript)
+    }));
+    assert!(result.is_ok(), "Verifier panicked on a malformed proof.");
+    assert!(result.unwrap().is_err());
+    true
+  };
+
+  let mut rng = test_rng();
+  let mut num_deserialized = 0;
+
+  // Single-byte corruptions of a valid proof, which mostly survive deserialization
+  for _ in 0..64 {
+    let mut bytes = proof_bytes.clone();
+    let i = rng.next_u32() as usize % bytes.len();
+    bytes[i] ^= (rng.next_u32() % 255 + 1) as u8;
+    num_deserialized += check(&bytes) as usize;
+  }
+
+  // Entirely random bytes, which mostly don't
+  for _ in 0..16 {
+    let mut bytes = vec![0u8; proof_bytes.len()];
+    rng.fill_bytes(&mut bytes);
+    check(&bytes);
+  }
+
+  assert!(num_deserialized > 0);
+}
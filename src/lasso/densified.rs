@@ -3,6 +3,7 @@ use ark_ff::PrimeField;
 
 use super::surge::{SparsePolyCommitmentGens, SparsePolynomialCommitment};
 use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::utils::instruction_utils::debug_assert_indices_in_range;
 use crate::utils::math::Math;
 
 pub struct DensifiedRepresentation<F: PrimeField, const C: usize> {
@@ -22,6 +23,9 @@ impl<F: PrimeField, const C: usize> DensifiedRepresentation<F, C> {
   pub fn from_lookup_indices(indices: &Vec<[usize; C]>, log_m: usize) -> Self {
     let s = indices.len().next_power_of_two();
     let m = log_m.pow2();
+    indices
+      .iter()
+      .for_each(|index| debug_assert_indices_in_range(index, m));
 
     let mut dim_usize: Vec<Vec<usize>> = Vec::with_capacity(C);
     let mut dim: Vec<DensePolynomial<F>> = Vec::with_capacity(C);
@@ -79,12 +83,20 @@ impl<F: PrimeField, const C: usize> DensifiedRepresentation<F, C> {
     &self,
     gens: &SparsePolyCommitmentGens<G>,
   ) -> SparsePolynomialCommitment<G> {
-    let (l_variate_polys_commitment, _) = self
-      .combined_l_variate_polys
-      .commit(&gens.gens_combined_l_variate, None);
-    let (log_m_variate_polys_commitment, _) = self
-      .combined_log_m_variate_polys
-      .commit(&gens.gens_combined_log_m_variate, None);
+    // dim < m and read timestamps < s; final timestamps are at most s
+    let l_variate_bits = self.log_m.max(self.s.log_2()).max(1);
+    let log_m_variate_bits = self.s.log_2() + 1;
+
+    let (l_variate_polys_commitment, _) = self.combined_l_variate_polys.commit_small(
+      &gens.gens_combined_l_variate,
+      None,
+      l_variate_bits,
+    );
+    let (log_m_variate_polys_commitment, _) = self.combined_log_m_variate_polys.commit_small(
+      &gens.gens_combined_log_m_variate,
+      None,
+      log_m_variate_bits,
+    );
 
     SparsePolynomialCommitment {
       l_variate_polys_commitment,
@@ -10,14 +10,14 @@ use crate::subtables::{
 };
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
+use crate::utils::maybe_par_iter;
 use crate::utils::random::RandomTape;
 use crate::utils::transcript::ProofTranscript;
 
 use ark_ec::CurveGroup;
-use ark_ff::{Field, PrimeField};
+use ark_ff::PrimeField;
 use ark_serialize::*;
 use ark_std::{One, Zero};
-use merlin::Transcript;
 use std::marker::Sync;
 
 #[cfg(feature = "multicore")]
@@ -53,19 +53,19 @@ where
   /// - `transcript`: The proof transcript, used for Fiat-Shamir.
   /// - `random_tape`: Randomness for dense polynomial commitments.
   #[tracing::instrument(skip_all, name = "MemoryChecking.prove")]
-  pub fn prove(
+  pub fn prove<T: ProofTranscript<G>>(
     dense: &DensifiedRepresentation<G::ScalarField, C>,
     r_mem_check: &(G::ScalarField, G::ScalarField),
     subtables: &Subtables<G::ScalarField, C, M, S>,
     gens: &SparsePolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
-  ) -> Self {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+  ) -> Result<Self, ProofVerifyError> {
+    <T as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
     let mut grand_products = subtables.to_grand_products(dense, r_mem_check);
     let (proof_prod_layer, rand_mem, rand_ops) =
-      ProductLayerProof::prove::<G>(&mut grand_products, transcript);
+      ProductLayerProof::prove::<G, _>(&mut grand_products, transcript)?;
 
     let proof_hash_layer = HashLayerProof::prove(
       (&rand_mem, &rand_ops),
@@ -76,10 +76,10 @@ where
       random_tape,
     );
 
-    MemoryCheckingProof {
+    Ok(MemoryCheckingProof {
       proof_prod_layer,
       proof_hash_layer,
-    }
+    })
   }
 
   /// Verifies that E_i polynomials are well-formed, i.e., that E_i(j) equals T_i[dim_i(j)] for all j ∈ {0, 1}^{log(m)},
@@ -93,16 +93,16 @@ where
   /// - `r_mem_check`: (gamma, tau) – Parameters for Reed-Solomon fingerprinting (see `hash_func` closure).
   /// - `s`: Sparsity, i.e. the number of lookups.
   /// - `transcript`: The proof transcript, used for Fiat-Shamir.
-  pub fn verify(
+  pub fn verify<T: ProofTranscript<G>>(
     &self,
     comm: &SparsePolynomialCommitment<G>,
     comm_derefs: &CombinedTableCommitment<G>,
     gens: &SparsePolyCommitmentGens<G>,
     r_mem_check: &(G::ScalarField, G::ScalarField),
     s: usize,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <T as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
     let (r_hash, r_multiset_check) = r_mem_check;
 
@@ -111,7 +111,7 @@ where
 
     let (claims_mem, rand_mem, claims_ops, rand_ops) = self
       .proof_prod_layer
-      .verify::<G>(num_ops, num_cells, transcript)?;
+      .verify::<G, _>(num_ops, num_cells, transcript)?;
 
     let claims: [(
       G::ScalarField,
@@ -142,11 +142,79 @@ where
     Ok(())
   }
 
+  /// Breaks down the compressed serialized size of the proof by component.
+  pub fn size_report(&self) -> SizeReport {
+    let prod_layer = &self.proof_prod_layer;
+    let hash_layer = &self.proof_hash_layer;
+    SizeReport {
+      multiset_hashes: prod_layer.grand_product_evals.compressed_size(),
+      read_write_grand_product: prod_layer.proof_ops.compressed_size(),
+      init_final_grand_product: prod_layer.proof_mem.compressed_size(),
+      hash_layer_evals: hash_layer.eval_dim.compressed_size()
+        + hash_layer.eval_read.compressed_size()
+        + hash_layer.eval_final.compressed_size()
+        + hash_layer.eval_derefs.compressed_size(),
+      ops_opening: hash_layer.proof_ops.compressed_size(),
+      mem_opening: hash_layer.proof_mem.compressed_size(),
+      derefs_opening: hash_layer.proof_derefs.compressed_size(),
+    }
+  }
+
   fn protocol_name() -> &'static [u8] {
     b"Lasso MemoryCheckingProof"
   }
 }
 
+/// Compressed serialized size, in bytes, of each component of a `MemoryCheckingProof`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeReport {
+  /// (init, read, write, final) grand product evaluations for every memory.
+  pub multiset_hashes: usize,
+  /// Batched grand product argument over the read and write sets.
+  pub read_write_grand_product: usize,
+  /// Batched grand product argument over the init and final sets.
+  pub init_final_grand_product: usize,
+  /// Claimed evaluations of dim, read, final and the E_i polynomials.
+  pub hash_layer_evals: usize,
+  /// Opening of the combined dim/read polynomial at the read/write point.
+  pub ops_opening: usize,
+  /// Opening of the combined final polynomial at the init/final point.
+  pub mem_opening: usize,
+  /// Opening of the combined E_i polynomial at the read/write point.
+  pub derefs_opening: usize,
+}
+
+impl SizeReport {
+  pub fn total(&self) -> usize {
+    self.multiset_hashes
+      + self.read_write_grand_product
+      + self.init_final_grand_product
+      + self.hash_layer_evals
+      + self.ops_opening
+      + self.mem_opening
+      + self.derefs_opening
+  }
+}
+
+/// Reed-Solomon fingerprint of `tuple` using consecutive powers of `gamma`:
+/// tuple[0] + tuple[1] * gamma + tuple[2] * gamma^2 + ... - tau.
+pub fn fingerprint<F: PrimeField>(tuple: &[F], gamma: &F, tau: &F) -> F {
+  tuple.iter().rev().fold(F::zero(), |acc, x| acc * gamma + x) - tau
+}
+
+/// Reed-Solomon fingerprint of `tuple` with an independent challenge per field:
+/// tuple[0] * gammas[0] + tuple[1] * gammas[1] + ... - tau. For tuples with many fields,
+/// separate challenges avoid collisions between fields whose values are correlated.
+pub fn fingerprint_indexed<F: PrimeField>(tuple: &[F], gammas: &[F], tau: &F) -> F {
+  assert_eq!(tuple.len(), gammas.len());
+  tuple
+    .iter()
+    .zip(gammas.iter())
+    .map(|(x, gamma)| *x * gamma)
+    .sum::<F>()
+    - tau
+}
+
 /// Contains grand product circuits to evaluate multi-set checks on memories.
 /// Evaluating each circuit is equivalent to computing the hash/fingerprint
 /// H_{\tau, \gamma} of the corresponding set.
@@ -249,7 +317,7 @@ impl<F: PrimeField> GrandProducts<F> {
     let (gamma, tau) = r_mem_check;
 
     // hash(a, v, t) = t * gamma^2 + v * gamma + a - tau
-    let hash_func = |a: &F, v: &F, t: &F| -> F { *t * gamma.square() + *v * *gamma + *a - tau };
+    let hash_func = |a: &F, v: &F, t: &F| -> F { fingerprint(&[*a, *v, *t], gamma, tau) };
 
     // init: M hash evaluations => log(M)-variate polynomial
     assert_eq!(eval_table.len(), final_i.len());
@@ -272,25 +340,20 @@ impl<F: PrimeField> GrandProducts<F> {
         .collect::<Vec<F>>(),
     );
 
-    // TODO(#30): Parallelize
-
     // read: s hash evaluations => log(s)-variate polynomial
     assert_eq!(dim_i.len(), read_i.len());
-
-    #[cfg(feature = "multicore")]
-    let num_ops = (0..dim_i.len()).into_par_iter();
-    #[cfg(not(feature = "multicore"))]
-    let num_ops = (0..dim_i.len()).iter();
     let grand_product_input_read = DensePolynomial::new(
-      num_ops.clone().map(|i| {
+      maybe_par_iter!(0..dim_i.len())
+        .map(|i| {
           // addr is given by dim_i, value is given by eval_table, and ts is given by read_ts
           hash_func(&dim_i[i], &eval_table[dim_i_usize[i]], &read_i[i])
         })
-        .collect::<Vec<F>>()
+        .collect::<Vec<F>>(),
     );
     // write: s hash evaluation => log(s)-variate polynomial
     let grand_product_input_write = DensePolynomial::new(
-      num_ops.map(|i| {
+      maybe_par_iter!(0..dim_i.len())
+        .map(|i| {
           // addr is given by dim_i, value is given by eval_table, and ts is given by write_ts = read_ts + 1
           hash_func(
             &dim_i[i],
@@ -335,15 +398,15 @@ where
   [(); S::NUM_MEMORIES]: Sized,
 {
   #[tracing::instrument(skip_all, name = "HashLayer.prove")]
-  fn prove(
+  fn prove<T: ProofTranscript<G>>(
     rand: (&Vec<G::ScalarField>, &Vec<G::ScalarField>),
     dense: &DensifiedRepresentation<G::ScalarField, C>,
     subtables: &Subtables<G::ScalarField, C, M, S>,
     gens: &SparsePolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
   ) -> Self {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <T as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
     let (rand_mem, rand_ops) = rand;
 
@@ -371,9 +434,9 @@ where
     evals_ops.extend(eval_read);
     evals_ops.resize(evals_ops.len().next_power_of_two(), G::ScalarField::zero());
 
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_ops", &evals_ops);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_ops", &evals_ops);
 
-    let challenges_ops = <Transcript as ProofTranscript<G>>::challenge_vector(
+    let challenges_ops = <T as ProofTranscript<G>>::challenge_vector(
       transcript,
       b"challenge_combine_n_to_one",
       evals_ops.len().log_2() as usize,
@@ -393,7 +456,7 @@ where
       joint_claim_eval_ops
     );
 
-    <Transcript as ProofTranscript<G>>::append_scalar(
+    <T as ProofTranscript<G>>::append_scalar(
       transcript,
       b"joint_claim_eval_ops",
       &joint_claim_eval_ops,
@@ -410,8 +473,8 @@ where
       random_tape,
     );
 
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_mem", &eval_final);
-    let challenges_mem = <Transcript as ProofTranscript<G>>::challenge_vector(
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_mem", &eval_final);
+    let challenges_mem = <T as ProofTranscript<G>>::challenge_vector(
       transcript,
       b"challenge_combine_two_to_one",
       eval_final.len().log_2() as usize,
@@ -431,7 +494,7 @@ where
       joint_claim_eval_mem
     );
 
-    <Transcript as ProofTranscript<G>>::append_scalar(
+    <T as ProofTranscript<G>>::append_scalar(
       transcript,
       b"joint_claim_eval_mem",
       &joint_claim_eval_mem,
@@ -494,7 +557,7 @@ where
     let hash_func = |a: &G::ScalarField,
                      v: &G::ScalarField,
                      t: &G::ScalarField|
-     -> G::ScalarField { *t * gamma.square() + *v * *gamma + *a - tau };
+     -> G::ScalarField { fingerprint(&[*a, *v, *t], gamma, tau) };
     // Note: this differs from the Lasso paper a little:
     // (t * gamma^2 + v * gamma + a) instead of (a * gamma^2 + v * gamma + t)
 
@@ -502,27 +565,39 @@ where
 
     // init
     let hash_init = hash_func(init_addr, init_memory, &G::ScalarField::zero());
-    assert_eq!(&hash_init, claim_init); // verify the last claim of the `init` grand product sumcheck
+    // verify the last claim of the `init` grand product sumcheck
+    if &hash_init != claim_init {
+      return Err(ProofVerifyError::FingerprintMismatch);
+    }
 
     // read
     let hash_read = hash_func(eval_dim, eval_deref, eval_read);
-    assert_eq!(hash_read, *claim_read); // verify the last claim of the `read` grand product sumcheck
+    // verify the last claim of the `read` grand product sumcheck
+    if hash_read != *claim_read {
+      return Err(ProofVerifyError::FingerprintMismatch);
+    }
 
     // write: shares addr, val with read
     let eval_write = *eval_read + G::ScalarField::one();
     let hash_write = hash_func(eval_dim, eval_deref, &eval_write);
-    assert_eq!(hash_write, *claim_write); // verify the last claim of the `write` grand product sumcheck
+    // verify the last claim of the `write` grand product sumcheck
+    if hash_write != *claim_write {
+      return Err(ProofVerifyError::FingerprintMismatch);
+    }
 
     // final: shares addr and val with init
     let eval_final_addr = init_addr;
     let eval_final_val = init_memory;
     let hash_final = hash_func(eval_final_addr, eval_final_val, eval_final);
-    assert_eq!(hash_final, *claim_final); // verify the last claim of the `final` grand product sumcheck
+    // verify the last claim of the `final` grand product sumcheck
+    if hash_final != *claim_final {
+      return Err(ProofVerifyError::FingerprintMismatch);
+    }
 
     Ok(())
   }
 
-  fn verify(
+  fn verify<T: ProofTranscript<G>>(
     &self,
     rand: (&Vec<G::ScalarField>, &Vec<G::ScalarField>),
     grand_product_claims: &[(
@@ -536,30 +611,33 @@ where
     table_eval_commitment: &CombinedTableCommitment<G>,
     r_hash: &G::ScalarField,
     r_multiset_check: &G::ScalarField,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <T as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
     let (rand_mem, rand_ops) = rand;
 
     // verify derefs at rand_ops
     // E_i(r_i''') ?= v_{E_i}
-    self.proof_derefs.verify(
-      rand_ops,
-      &self.eval_derefs,
-      &gens.gens_derefs,
-      table_eval_commitment,
-      transcript,
-    )?;
+    self
+      .proof_derefs
+      .verify(
+        rand_ops,
+        &self.eval_derefs,
+        &gens.gens_derefs,
+        table_eval_commitment,
+        transcript,
+      )
+      .map_err(|_| ProofVerifyError::OpeningFailed)?;
 
     let mut evals_ops: Vec<G::ScalarField> = Vec::new();
     evals_ops.extend(self.eval_dim);
     evals_ops.extend(self.eval_read);
     evals_ops.resize(evals_ops.len().next_power_of_two(), G::ScalarField::zero());
 
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_ops", &evals_ops);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_ops", &evals_ops);
 
-    let challenges_ops = <Transcript as ProofTranscript<G>>::challenge_vector(
+    let challenges_ops = <T as ProofTranscript<G>>::challenge_vector(
       transcript,
       b"challenge_combine_n_to_one",
       evals_ops.len().log_2() as usize,
@@ -574,7 +652,7 @@ where
     let joint_claim_eval_ops = poly_evals_ops[0];
     let mut r_joint_ops = challenges_ops;
     r_joint_ops.extend(rand_ops);
-    <Transcript as ProofTranscript<G>>::append_scalar(
+    <T as ProofTranscript<G>>::append_scalar(
       transcript,
       b"joint_claim_eval_ops",
       &joint_claim_eval_ops,
@@ -582,20 +660,19 @@ where
 
     // dim_i(r_i''') ?= v_i
     // read_i(r_i''') ?= v_{read_i}
-    self.proof_ops.verify_plain(
-      &gens.gens_combined_l_variate,
-      transcript,
-      &r_joint_ops,
-      &joint_claim_eval_ops,
-      &comm.l_variate_polys_commitment,
-    )?;
+    self
+      .proof_ops
+      .verify_plain(
+        &gens.gens_combined_l_variate,
+        transcript,
+        &r_joint_ops,
+        &joint_claim_eval_ops,
+        &comm.l_variate_polys_commitment,
+      )
+      .map_err(|_| ProofVerifyError::OpeningFailed)?;
 
-    <Transcript as ProofTranscript<G>>::append_scalars(
-      transcript,
-      b"claim_evals_mem",
-      &self.eval_final,
-    );
-    let challenges_mem = <Transcript as ProofTranscript<G>>::challenge_vector(
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"claim_evals_mem", &self.eval_final);
+    let challenges_mem = <T as ProofTranscript<G>>::challenge_vector(
       transcript,
       b"challenge_combine_two_to_one",
       self.eval_final.len().log_2() as usize,
@@ -610,20 +687,23 @@ where
     let joint_claim_eval_mem = poly_evals_mem[0];
     let mut r_joint_mem = challenges_mem;
     r_joint_mem.extend(rand_mem);
-    <Transcript as ProofTranscript<G>>::append_scalar(
+    <T as ProofTranscript<G>>::append_scalar(
       transcript,
       b"joint_claim_eval_mem",
       &joint_claim_eval_mem,
     );
 
     // final_i(r_i'') ?= v_{final_i}
-    self.proof_mem.verify_plain(
-      &gens.gens_combined_log_m_variate,
-      transcript,
-      &r_joint_mem,
-      &joint_claim_eval_mem,
-      &comm.log_m_variate_polys_commitment,
-    )?;
+    self
+      .proof_mem
+      .verify_plain(
+        &gens.gens_combined_log_m_variate,
+        transcript,
+        &r_joint_mem,
+        &joint_claim_eval_mem,
+        &comm.log_m_variate_polys_commitment,
+      )
+      .map_err(|_| ProofVerifyError::OpeningFailed)?;
 
     // verify the claims from the product layer
     let init_addr = IdentityPolynomial::new(rand_mem.len()).evaluate(rand_mem);
@@ -664,6 +744,22 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
     b"Lasso ProductLayerProof"
   }
 
+  /// Checks that init * write == read * final for the grand product evaluations of memory `memory_index`.
+  fn check_multiset_equality(
+    memory_index: usize,
+    evals: &(F, F, F, F),
+  ) -> Result<(), ProofVerifyError> {
+    let (hash_init, hash_read, hash_write, hash_final) = evals;
+    if *hash_init * hash_write == *hash_read * hash_final {
+      Ok(())
+    } else {
+      Err(ProofVerifyError::MultisetMismatch {
+        memory: memory_index,
+        hashes: format!("{evals:?}"),
+      })
+    }
+  }
+
   /// Performs grand product argument proofs required for memory-checking.
   /// Batches everything into two instances of BatchedGrandProductArgument.
   ///
@@ -671,38 +767,33 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
   /// - `grand_products`: The grand product circuits whose evaluations are proven.
   /// - `transcript`: The proof transcript, used for Fiat-Shamir.
   #[tracing::instrument(skip_all, name = "ProductLayer.prove")]
-  pub fn prove<G>(
-    grand_products: &mut Vec<GrandProducts<F>>,
-    transcript: &mut Transcript,
-  ) -> (Self, Vec<F>, Vec<F>)
+  pub fn prove<G, T: ProofTranscript<G>>(
+    grand_products: &mut [GrandProducts<F>],
+    transcript: &mut T,
+  ) -> Result<(Self, Vec<F>, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <T as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
     let grand_product_evals: [(F, F, F, F); NUM_MEMORIES] = std::array::from_fn(|i| {
-      let hash_init = grand_products[i].init.evaluate();
-      let hash_read = grand_products[i].read.evaluate();
-      let hash_write = grand_products[i].write.evaluate();
-      let hash_final = grand_products[i].r#final.evaluate();
-
-      assert_eq!(hash_init * hash_write, hash_read * hash_final);
+      (
+        grand_products[i].init.evaluate(),
+        grand_products[i].read.evaluate(),
+        grand_products[i].write.evaluate(),
+        grand_products[i].r#final.evaluate(),
+      )
+    });
 
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_init", &hash_init);
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_read", &hash_read);
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_write",
-        &hash_write,
-      );
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_final",
-        &hash_final,
-      );
+    for (i, evals) in grand_product_evals.iter().enumerate() {
+      Self::check_multiset_equality(i, evals)?;
+      let (hash_init, hash_read, hash_write, hash_final) = *evals;
 
-      (hash_init, hash_read, hash_write, hash_final)
-    });
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_init", &hash_init);
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_read", &hash_read);
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_write", &hash_write);
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_final", &hash_final);
+    }
 
     let mut read_write_grand_products: Vec<&mut GrandProductCircuit<F>> = grand_products
       .iter_mut()
@@ -710,7 +801,7 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
       .collect();
 
     let (proof_ops, rand_ops) =
-      BatchedGrandProductArgument::<F>::prove::<G>(&mut read_write_grand_products, transcript);
+      BatchedGrandProductArgument::<F>::prove::<G, _>(&mut read_write_grand_products, transcript);
 
     let mut init_final_grand_products: Vec<&mut GrandProductCircuit<F>> = grand_products
       .iter_mut()
@@ -719,7 +810,7 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
 
     // produce a batched proof of memory-related product circuits
     let (proof_mem, rand_mem) =
-      BatchedGrandProductArgument::<F>::prove::<G>(&mut init_final_grand_products, transcript);
+      BatchedGrandProductArgument::<F>::prove::<G, _>(&mut init_final_grand_products, transcript);
 
     let product_layer_proof = ProductLayerProof {
       grand_product_evals,
@@ -727,36 +818,32 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
       proof_ops,
     };
 
-    (product_layer_proof, rand_mem, rand_ops)
+    Ok((product_layer_proof, rand_mem, rand_ops))
   }
 
-  pub fn verify<G>(
+  pub fn verify<G, T: ProofTranscript<G>>(
     &self,
     num_ops: usize,
     num_cells: usize,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> Result<(Vec<F>, Vec<F>, Vec<F>, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    // Reject malformed proofs before doing any expensive work
+    self.proof_ops.check_shape(2 * NUM_MEMORIES, num_ops)?;
+    self.proof_mem.check_shape(2 * NUM_MEMORIES, num_cells)?;
 
-    for (hash_init, hash_read, hash_write, hash_final) in self.grand_product_evals {
-      // Multiset equality check
-      assert_eq!(hash_init * hash_write, hash_read * hash_final);
+    <T as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_init", &hash_init);
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_read", &hash_read);
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_write",
-        &hash_write,
-      );
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_final",
-        &hash_final,
-      );
+    for (i, evals) in self.grand_product_evals.iter().enumerate() {
+      Self::check_multiset_equality(i, evals)?;
+      let (hash_init, hash_read, hash_write, hash_final) = *evals;
+
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_init", &hash_init);
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_read", &hash_read);
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_write", &hash_write);
+      <T as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_final", &hash_final);
     }
 
     let read_write_claims: Vec<F> = self
@@ -768,7 +855,7 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
     let (claims_ops, rand_ops) =
       self
         .proof_ops
-        .verify::<G, Transcript>(&read_write_claims, num_ops, transcript);
+        .verify::<G, T>(&read_write_claims, num_ops, transcript)?;
 
     let init_final_claims: Vec<F> = self
       .grand_product_evals
@@ -779,7 +866,7 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
     let (claims_mem, rand_mem) =
       self
         .proof_mem
-        .verify::<G, Transcript>(&init_final_claims, num_cells, transcript);
+        .verify::<G, T>(&init_final_claims, num_cells, transcript)?;
 
     Ok((claims_mem, rand_mem, claims_ops, rand_ops))
   }
@@ -787,9 +874,66 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
 
 #[cfg(test)]
 mod test {
-  use ark_curve25519::Fr;
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+  use merlin::Transcript;
 
   use super::*;
+  use crate::utils::compute_final_timestamps;
+
+  /// Whether the leaves of the grand products satisfy init ∪ write == read ∪ final as multisets.
+  /// Unlike comparing the grand products, this pinpoints which leaves are unmatched.
+  fn multiset_leaves_consistent(
+    eval_table: &[Fr],
+    dim_i: &DensePolynomial<Fr>,
+    dim_i_usize: &[usize],
+    read_i: &DensePolynomial<Fr>,
+    final_i: &DensePolynomial<Fr>,
+    r_mem_check: &(Fr, Fr),
+  ) -> Result<(), String> {
+    let (init, read, write, r#final) = GrandProducts::build_grand_product_inputs(
+      eval_table,
+      dim_i,
+      dim_i_usize,
+      read_i,
+      final_i,
+      r_mem_check,
+    );
+    let leaves = |a: &DensePolynomial<Fr>, b: &DensePolynomial<Fr>| {
+      let mut leaves: Vec<Fr> = (0..a.len()).map(|i| a[i]).collect();
+      leaves.extend((0..b.len()).map(|i| b[i]));
+      leaves.sort();
+      leaves
+    };
+    let write_set = leaves(&init, &write);
+    let read_set = leaves(&read, &r#final);
+    if write_set == read_set {
+      Ok(())
+    } else {
+      let unmatched = write_set
+        .iter()
+        .filter(|leaf| !read_set.contains(leaf))
+        .count();
+      Err(format!(
+        "{unmatched} of {} init/write leaves have no matching read/final leaf",
+        write_set.len()
+      ))
+    }
+  }
+
+  fn assert_multiset_leaves_consistent(
+    eval_table: &[Fr],
+    dim_i: &DensePolynomial<Fr>,
+    dim_i_usize: &[usize],
+    read_i: &DensePolynomial<Fr>,
+    final_i: &DensePolynomial<Fr>,
+    r_mem_check: &(Fr, Fr),
+  ) {
+    if let Err(msg) =
+      multiset_leaves_consistent(eval_table, dim_i, dim_i_usize, read_i, final_i, r_mem_check)
+    {
+      panic!("Memory checking leaves are inconsistent: {msg}");
+    }
+  }
 
   #[test]
   fn test() {
@@ -820,6 +964,14 @@ mod test {
     ]);
     let r_mem_check = (Fr::from(100), Fr::from(200));
 
+    assert_multiset_leaves_consistent(
+      &eval_table,
+      &dim_i,
+      &dim_i_usize,
+      &read_i,
+      &final_i,
+      &r_mem_check,
+    );
     let _gp = GrandProducts::new(
       &eval_table,
       &dim_i,
@@ -829,4 +981,368 @@ mod test {
       &r_mem_check,
     );
   }
+
+  /// `num_memories` copies of the memory from `test`.
+  fn trivial_grand_products(num_memories: usize) -> Vec<GrandProducts<Fr>> {
+    let eval_table: Vec<Fr> = (10..18).map(Fr::from).collect();
+    let dim_i = DensePolynomial::new(vec![Fr::from(1), Fr::from(2), Fr::from(1), Fr::from(5)]);
+    let dim_i_usize = vec![1usize, 2, 1, 5];
+    let read_i = DensePolynomial::new(vec![Fr::from(0), Fr::from(0), Fr::from(1), Fr::from(0)]);
+    let final_i = DensePolynomial::from_usize(&compute_final_timestamps(&dim_i_usize, 8));
+    let r_mem_check = (Fr::from(100), Fr::from(200));
+    assert_multiset_leaves_consistent(
+      &eval_table,
+      &dim_i,
+      &dim_i_usize,
+      &read_i,
+      &final_i,
+      &r_mem_check,
+    );
+
+    (0..num_memories)
+      .map(|_| {
+        GrandProducts::new(
+          &eval_table,
+          &dim_i,
+          &dim_i_usize,
+          &read_i,
+          &final_i,
+          &r_mem_check,
+        )
+      })
+      .collect()
+  }
+
+  /// Proves and verifies the product layer, checking that prover and verifier derive the same
+  /// challenges and leave their transcripts in the same state.
+  fn product_layer_prove_verify<T: ProofTranscript<G1Projective>>(
+    new_transcript: impl Fn() -> T,
+  ) -> Vec<Fr> {
+    let mut grand_products = trivial_grand_products(2);
+
+    let mut prover_transcript = new_transcript();
+    let (proof, prover_rand_mem, prover_rand_ops) =
+      ProductLayerProof::<Fr, 2>::prove::<G1Projective, _>(
+        &mut grand_products,
+        &mut prover_transcript,
+      )
+      .unwrap();
+
+    let mut verifier_transcript = new_transcript();
+    let (_, verifier_rand_mem, _, verifier_rand_ops) = proof
+      .verify::<G1Projective, _>(4, 8, &mut verifier_transcript)
+      .unwrap();
+    assert_eq!(prover_rand_mem, verifier_rand_mem);
+    assert_eq!(prover_rand_ops, verifier_rand_ops);
+
+    let prover_challenge = prover_transcript.challenge_scalar(b"test");
+    let verifier_challenge = verifier_transcript.challenge_scalar(b"test");
+    assert_eq!(prover_challenge, verifier_challenge);
+
+    [prover_rand_mem, prover_rand_ops, vec![prover_challenge]].concat()
+  }
+
+  #[test]
+  fn product_layer_generic_transcript() {
+    product_layer_prove_verify(|| Transcript::new(b"example"));
+  }
+
+  #[cfg(feature = "blake3")]
+  #[test]
+  fn product_layer_blake3_transcript() {
+    use crate::utils::transcript::Blake3Transcript;
+
+    let merlin_challenges = product_layer_prove_verify(|| Transcript::new(b"example"));
+    let blake3_challenges = product_layer_prove_verify(|| Blake3Transcript::new(b"example"));
+    assert_ne!(merlin_challenges, blake3_challenges);
+  }
+
+  #[test]
+  fn product_layer_reports_mismatched_memory() {
+    let mut grand_products = trivial_grand_products(3);
+
+    let mut prover_transcript = Transcript::new(b"example");
+    let (mut proof, _, _) = ProductLayerProof::<Fr, 3>::prove::<G1Projective, _>(
+      &mut grand_products,
+      &mut prover_transcript,
+    )
+    .unwrap();
+
+    let mut verifier_transcript = Transcript::new(b"example");
+    assert!(proof
+      .verify::<G1Projective, _>(4, 8, &mut verifier_transcript)
+      .is_ok());
+
+    // Claimed number of ops doesn't match the proof
+    let mut verifier_transcript = Transcript::new(b"example");
+    assert!(matches!(
+      proof.verify::<G1Projective, _>(8, 8, &mut verifier_transcript),
+      Err(ProofVerifyError::InvalidShape)
+    ));
+
+    proof.grand_product_evals[1].2 += Fr::from(1);
+    let mut verifier_transcript = Transcript::new(b"example");
+    assert!(matches!(
+      proof.verify::<G1Projective, _>(4, 8, &mut verifier_transcript),
+      Err(ProofVerifyError::MultisetMismatch { memory: 1, .. })
+    ));
+  }
+
+  #[test]
+  fn product_layer_prover_reports_mismatched_memory() {
+    let mut grand_products = trivial_grand_products(3);
+
+    // Memory 1 claims a read timestamp that was never written
+    let eval_table: Vec<Fr> = (10..18).map(Fr::from).collect();
+    let dim_i_usize = vec![1usize, 2, 1, 5];
+    let dim_i = DensePolynomial::from_usize(&dim_i_usize);
+    let read_i = DensePolynomial::new(vec![Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(0)]);
+    let final_i = DensePolynomial::from_usize(&compute_final_timestamps(&dim_i_usize, 8));
+    grand_products[1] = GrandProducts::new(
+      &eval_table,
+      &dim_i,
+      &dim_i_usize,
+      &read_i,
+      &final_i,
+      &(Fr::from(100), Fr::from(200)),
+    );
+
+    let mut prover_transcript = Transcript::new(b"example");
+    assert!(matches!(
+      ProductLayerProof::<Fr, 3>::prove::<G1Projective, _>(
+        &mut grand_products,
+        &mut prover_transcript,
+      ),
+      Err(ProofVerifyError::MultisetMismatch { memory: 1, .. })
+    ));
+  }
+
+  #[test]
+  fn densified_leaves_consistent() {
+    use crate::subtables::and::AndSubtableStrategy;
+    use crate::utils::test::gen_indices;
+
+    const C: usize = 2;
+    const M: usize = 16;
+    let dense =
+      DensifiedRepresentation::<Fr, C>::from_lookup_indices(&gen_indices::<C>(32, M), M.log_2());
+    let [eval_table] = <AndSubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    let r_mem_check = (Fr::from(100), Fr::from(200));
+
+    for j in 0..C {
+      assert_multiset_leaves_consistent(
+        &eval_table,
+        &dense.dim[j],
+        &dense.dim_usize[j],
+        &dense.read[j],
+        &dense.r#final[j],
+        &r_mem_check,
+      );
+    }
+
+    // Swapping the final timestamps of two touched cells breaks the correspondence
+    let (a, b) = (dense.dim_usize[0][0], dense.dim_usize[0][1]);
+    let mut final_ts: Vec<Fr> = (0..M).map(|i| dense.r#final[0][i]).collect();
+    if final_ts[a] == final_ts[b] {
+      final_ts[a] += Fr::from(1);
+    } else {
+      final_ts.swap(a, b);
+    }
+    assert!(multiset_leaves_consistent(
+      &eval_table,
+      &dense.dim[0],
+      &dense.dim_usize[0],
+      &dense.read[0],
+      &DensePolynomial::new(final_ts),
+      &r_mem_check,
+    )
+    .is_err());
+  }
+
+  /// The read/write leaves match a plain serial computation, whether or not `multicore` is enabled.
+  #[test]
+  fn grand_product_inputs_match_serial() {
+    use crate::utils::test::gen_indices;
+
+    const C: usize = 1;
+    const M: usize = 16;
+    let dense =
+      DensifiedRepresentation::<Fr, C>::from_lookup_indices(&gen_indices::<C>(64, M), M.log_2());
+    let eval_table: Vec<Fr> = (0..M as u64).map(Fr::from).collect();
+    let r_mem_check = (Fr::from(100), Fr::from(200));
+    let (gamma, tau) = &r_mem_check;
+
+    let (_, read, write, _) = GrandProducts::build_grand_product_inputs(
+      &eval_table,
+      &dense.dim[0],
+      &dense.dim_usize[0],
+      &dense.read[0],
+      &dense.r#final[0],
+      &r_mem_check,
+    );
+    for i in 0..dense.dim[0].len() {
+      let (a, v, t) = (
+        dense.dim[0][i],
+        eval_table[dense.dim_usize[0][i]],
+        dense.read[0][i],
+      );
+      assert_eq!(read[i], fingerprint(&[a, v, t], gamma, tau));
+      assert_eq!(write[i], fingerprint(&[a, v, t + Fr::one()], gamma, tau));
+    }
+  }
+
+  #[test]
+  fn fingerprint_indexed_matches_powers() {
+    use ark_ff::Field;
+    use ark_std::{test_rng, UniformRand};
+
+    let mut rng = test_rng();
+    let (gamma, tau) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let (a, v, t) = (Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+    let expected = t * gamma.square() + v * gamma + a - tau;
+    assert_eq!(fingerprint(&[a, v, t], &gamma, &tau), expected);
+    assert_eq!(
+      fingerprint_indexed(&[a, v, t], &[Fr::one(), gamma, gamma.square()], &tau),
+      expected
+    );
+  }
+
+  #[test]
+  fn fingerprint_arity() {
+    use ark_ff::Field;
+    use ark_std::{test_rng, UniformRand};
+
+    let mut rng = test_rng();
+    let (gamma, tau) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    let tuple: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+    let gammas: Vec<Fr> = (0..4).map(|i| gamma.pow([i as u64])).collect();
+
+    for arity in [3, 4] {
+      let expected: Fr = (0..arity).map(|i| tuple[i] * gammas[i]).sum::<Fr>() - tau;
+      assert_eq!(fingerprint(&tuple[..arity], &gamma, &tau), expected);
+      assert_eq!(
+        fingerprint_indexed(&tuple[..arity], &gammas[..arity], &tau),
+        expected
+      );
+    }
+  }
+
+  #[test]
+  fn multiset_equality_randomized() {
+    use crate::utils::test::gen_multiset_hashes;
+    use ark_std::test_rng;
+
+    let mut rng = test_rng();
+    for memory_index in 0..256 {
+      let evals = gen_multiset_hashes::<Fr>(&mut rng, true);
+      assert!(ProductLayerProof::<Fr, 1>::check_multiset_equality(memory_index, &evals).is_ok());
+
+      let evals = gen_multiset_hashes::<Fr>(&mut rng, false);
+      match ProductLayerProof::<Fr, 1>::check_multiset_equality(memory_index, &evals) {
+        Err(ProofVerifyError::MultisetMismatch { memory, hashes }) => {
+          assert_eq!(memory, memory_index);
+          assert_eq!(hashes, format!("{evals:?}"));
+        }
+        other => panic!("expected MultisetMismatch, got {other:?}"),
+      }
+    }
+  }
+
+  #[test]
+  fn size_report_sums_to_total() {
+    use crate::subtables::and::AndSubtableStrategy;
+    use crate::utils::test::gen_indices;
+
+    const C: usize = 2;
+    const M: usize = 16;
+    const S: usize = 8;
+    let log_m = M.log_2();
+
+    let dense =
+      DensifiedRepresentation::<Fr, C>::from_lookup_indices(&gen_indices::<C>(S, M), log_m);
+    let gens = SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, C, log_m);
+    let subtables = Subtables::<Fr, C, M, AndSubtableStrategy>::new(&dense.dim_usize, dense.s);
+
+    let mut random_tape = RandomTape::new(b"proof");
+    let mut prover_transcript = Transcript::new(b"example");
+    let proof = MemoryCheckingProof::<G1Projective, C, M, AndSubtableStrategy>::prove(
+      &dense,
+      &(Fr::from(100), Fr::from(200)),
+      &subtables,
+      &gens,
+      &mut prover_transcript,
+      &mut random_tape,
+    )
+    .unwrap();
+
+    let report = proof.size_report();
+    assert_eq!(report.multiset_hashes, C * 4 * Fr::zero().compressed_size());
+    assert_eq!(
+      report.read_write_grand_product,
+      BatchedGrandProductArgument::<Fr>::proof_size_bytes(2 * C, S)
+    );
+    assert_eq!(
+      report.init_final_grand_product,
+      BatchedGrandProductArgument::<Fr>::proof_size_bytes(2 * C, M)
+    );
+    assert_eq!(report.total(), proof.compressed_size());
+  }
+
+  #[test]
+  fn hash_layer_reports_failure_mode() {
+    use crate::subtables::and::AndSubtableStrategy;
+    use crate::utils::test::gen_indices;
+
+    const C: usize = 2;
+    const M: usize = 16;
+    const S: usize = 8;
+    type Proof = MemoryCheckingProof<G1Projective, C, M, AndSubtableStrategy>;
+    let log_m = M.log_2();
+
+    let dense =
+      DensifiedRepresentation::<Fr, C>::from_lookup_indices(&gen_indices::<C>(S, M), log_m);
+    let gens = SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, C, log_m);
+    let comm = dense.commit::<G1Projective>(&gens);
+    let subtables = Subtables::<Fr, C, M, AndSubtableStrategy>::new(&dense.dim_usize, dense.s);
+    let comm_derefs = subtables.commit(&gens.gens_derefs);
+    let r_mem_check = (Fr::from(100), Fr::from(200));
+
+    let mut random_tape = RandomTape::new(b"proof");
+    let mut prover_transcript = Transcript::new(b"example");
+    let mut proof = Proof::prove(
+      &dense,
+      &r_mem_check,
+      &subtables,
+      &gens,
+      &mut prover_transcript,
+      &mut random_tape,
+    )
+    .unwrap();
+
+    let verify = |proof: &Proof, r_mem_check: &(Fr, Fr)| {
+      let mut verifier_transcript = Transcript::new(b"example");
+      proof.verify(
+        &comm,
+        &comm_derefs,
+        &gens,
+        r_mem_check,
+        S,
+        &mut verifier_transcript,
+      )
+    };
+    assert!(verify(&proof, &r_mem_check).is_ok());
+
+    // Openings still verify, but the fingerprints were computed with different (gamma, tau)
+    assert!(matches!(
+      verify(&proof, &(Fr::from(101), Fr::from(200))),
+      Err(ProofVerifyError::FingerprintMismatch)
+    ));
+
+    proof.proof_hash_layer.eval_derefs[0] += Fr::from(1);
+    assert!(matches!(
+      verify(&proof, &r_mem_check),
+      Err(ProofVerifyError::OpeningFailed)
+    ));
+  }
 }
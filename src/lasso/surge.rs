@@ -8,7 +8,8 @@ use crate::poly::dense_mlpoly::{DensePolynomial, PolyCommitment, PolyCommitmentG
 use crate::poly::eq_poly::EqPolynomial;
 use crate::subprotocols::sumcheck::SumcheckInstanceProof;
 use crate::subtables::{
-  CombinedTableCommitment, CombinedTableEvalProof, SubtableStrategy, Subtables,
+  CombinedTableCommitment, CombinedTableEvalProof, SubtablePreprocessing, SubtableStrategy,
+  Subtables,
 };
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
@@ -112,25 +113,55 @@ where
 {
   /// Prove an opening of the Sparse Matrix Polynomial
   /// - `dense`: DensifiedRepresentation
+  /// - `commitment`: Commitment to `dense`, absorbed into the transcript before any challenges
   /// - `r`: log(s) sized coordinates at which to prove the evaluation of eq in the primary sumcheck
   /// - `eval`: evaluation of \widetilde{M}(r = (r_1, ..., r_logM))
   /// - `gens`: Commitment generator
-  #[tracing::instrument(skip_all, name = "SparsePoly.prove")]
+  ///
+  /// Fails with `MultisetMismatch` if the memory-checking multisets of `dense` do not match.
   pub fn prove(
     dense: &mut DensifiedRepresentation<G::ScalarField, C>,
+    commitment: &SparsePolynomialCommitment<G>,
+    r: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+    random_tape: &mut RandomTape<G>,
+  ) -> Result<Self, ProofVerifyError>
+  where
+    [(); S::NUM_SUBTABLES]: Sized,
+  {
+    Self::prove_preprocessed(
+      dense,
+      commitment,
+      &SubtablePreprocessing::new(),
+      r,
+      gens,
+      transcript,
+      random_tape,
+    )
+  }
+
+  /// Same as `prove`, but reads the subtables from `preprocessing` instead of materializing them.
+  #[tracing::instrument(skip_all, name = "SparsePoly.prove")]
+  pub fn prove_preprocessed(
+    dense: &mut DensifiedRepresentation<G::ScalarField, C>,
+    commitment: &SparsePolynomialCommitment<G>,
+    preprocessing: &SubtablePreprocessing<G::ScalarField, C, M, S>,
     r: &Vec<G::ScalarField>,
     gens: &SparsePolyCommitmentGens<G>,
     transcript: &mut Transcript,
     random_tape: &mut RandomTape<G>,
-  ) -> Self
+  ) -> Result<Self, ProofVerifyError>
   where
     [(); S::NUM_SUBTABLES]: Sized,
   {
     <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    commitment.append_to_transcript(b"sparse_poly_commitment", transcript);
 
     assert_eq!(r.len(), log2(dense.s) as usize);
 
-    let subtables = Subtables::<_, C, M, S>::new(&dense.dim_usize, dense.s);
+    let subtables =
+      Subtables::<_, C, M, S>::new_preprocessed(preprocessing, &dense.dim_usize, dense.s);
 
     // commit to non-deterministic choices of the prover
     let comm_derefs = {
@@ -195,10 +226,10 @@ where
         gens,
         transcript,
         random_tape,
-      )
+      )?
     };
 
-    Self {
+    Ok(Self {
       comm_derefs,
       primary_sumcheck: PrimarySumcheck {
         proof: primary_sumcheck_proof,
@@ -207,7 +238,7 @@ where
         proof_derefs,
       },
       memory_check,
-    }
+    })
   }
 
   #[tracing::instrument(skip_all, name = "SparsePoly.verify")]
@@ -220,6 +251,8 @@ where
   ) -> Result<(), ProofVerifyError> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
 
+    commitment.append_to_transcript(b"sparse_poly_commitment", transcript);
+
     debug_assert_eq!(eq_randomness.len(), log2(commitment.s) as usize);
 
     // add claims to transcript and obtain challenges for randomized mem-check circuit
@@ -242,11 +275,9 @@ where
 
     // Verify that eq(r, r_z) * g(E_1(r_z) * ... * E_c(r_z)) = claim_last
     let eq_eval = EqPolynomial::new(eq_randomness.clone()).evaluate(&r_z);
-    assert_eq!(
-      eq_eval * S::combine_lookups(&self.primary_sumcheck.eval_derefs),
-      claim_last,
-      "Primary sumcheck check failed."
-    );
+    if eq_eval * S::combine_lookups(&self.primary_sumcheck.eval_derefs) != claim_last {
+      return Err(ProofVerifyError::ClaimMismatch);
+    }
 
     self.primary_sumcheck.proof_derefs.verify(
       &r_z,
@@ -274,3 +305,39 @@ where
     b"Lasso SparsePolynomialEvaluationProof"
   }
 }
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+
+  use super::*;
+  use crate::utils::test::gen_indices;
+
+  #[test]
+  fn commitment_binds_memory_checking_challenges() {
+    const C: usize = 2;
+    const M: usize = 16;
+    const S: usize = 16;
+    let log_m = M.log_2();
+
+    let gens = SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, C, log_m);
+    let commit = |indices: &Vec<[usize; C]>| {
+      DensifiedRepresentation::<Fr, C>::from_lookup_indices(indices, log_m)
+        .commit::<G1Projective>(&gens)
+    };
+    let gamma = |commitment: &SparsePolynomialCommitment<G1Projective>| -> Vec<Fr> {
+      let mut transcript = Transcript::new(b"example");
+      commitment.append_to_transcript(b"sparse_poly_commitment", &mut transcript);
+      <Transcript as ProofTranscript<G1Projective>>::challenge_vector(
+        &mut transcript,
+        b"challenge_r_hash",
+        2,
+      )
+    };
+
+    let indices = gen_indices::<C>(S, M);
+    let other_indices: Vec<[usize; C]> = (0..S).map(|i| [i % M; C]).collect();
+    assert_eq!(gamma(&commit(&indices)), gamma(&commit(&indices)));
+    assert_ne!(gamma(&commit(&indices)), gamma(&commit(&other_indices)));
+  }
+}
@@ -12,5 +12,10 @@ mod subprotocols;
 pub mod subtables;
 mod utils;
 
+#[cfg(feature = "gpu-msm")]
+pub use msm::backend::{register_msm_backend, MsmBackend};
+#[cfg(feature = "profiling")]
+pub use utils::profiling::{summarize, SpanSummary};
+
 #[cfg(test)]
 mod e2e_test;
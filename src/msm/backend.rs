@@ -0,0 +1,141 @@
+use ark_ec::CurveGroup;
+
+#[cfg(feature = "ark-msm")]
+use ark_ec::VariableBaseMSM;
+
+#[cfg(not(feature = "ark-msm"))]
+use super::VariableBaseMSM;
+
+/// An implementation of multi-scalar multiplication that commitment code dispatches to.
+pub trait MsmBackend<G: CurveGroup>: Sync {
+  fn name(&self) -> &'static str;
+  /// Whether this backend should compute an MSM over `num_bases` bases, e.g. `false` below the
+  /// size at which offloading to a device pays off.
+  fn supports(&self, num_bases: usize) -> bool;
+  fn msm(&self, bases: &[G::Affine], scalars: &[G::ScalarField]) -> G;
+  /// MSM over scalars known to fit in `max_bits` bits, such as lookup indices and timestamps.
+  /// Defaults to [`MsmBackend::msm`].
+  fn msm_small(&self, bases: &[G::Affine], scalars: &[G::ScalarField], max_bits: usize) -> G {
+    let _ = max_bits;
+    self.msm(bases, scalars)
+  }
+}
+
+/// Default backend: [`VariableBaseMSM`] on the CPU (arkworks' MSM with the `ark-msm` feature).
+pub struct CpuMsm;
+
+impl<G: CurveGroup> MsmBackend<G> for CpuMsm {
+  fn name(&self) -> &'static str {
+    "cpu"
+  }
+
+  fn supports(&self, _num_bases: usize) -> bool {
+    true
+  }
+
+  fn msm(&self, bases: &[G::Affine], scalars: &[G::ScalarField]) -> G {
+    VariableBaseMSM::msm(bases, scalars).unwrap()
+  }
+
+  #[cfg(not(feature = "ark-msm"))]
+  fn msm_small(&self, bases: &[G::Affine], scalars: &[G::ScalarField], max_bits: usize) -> G {
+    assert_eq!(bases.len(), scalars.len());
+    VariableBaseMSM::msm_small(bases, scalars, max_bits)
+  }
+}
+
+/// Registered external backends, at most one per curve. Each entry holds the curve's `TypeId` and
+/// a boxed `&'static dyn MsmBackend<G>` for that curve.
+#[cfg(feature = "gpu-msm")]
+static EXTERNAL_BACKENDS: std::sync::RwLock<
+  Vec<(std::any::TypeId, Box<dyn std::any::Any + Send + Sync>)>,
+> = std::sync::RwLock::new(Vec::new());
+
+/// Registers an external (e.g. GPU) backend for the curve `G`, replacing any backend previously
+/// registered for `G`. It is used by [`msm_backend`] in place of the CPU backend for MSMs over `G`
+/// it reports support for.
+#[cfg(feature = "gpu-msm")]
+pub fn register_msm_backend<G: CurveGroup>(backend: &'static dyn MsmBackend<G>) {
+  let curve = std::any::TypeId::of::<G>();
+  let mut backends = EXTERNAL_BACKENDS.write().unwrap();
+  backends.retain(|(registered, _)| *registered != curve);
+  backends.push((curve, Box::new(backend)));
+}
+
+/// The backend an MSM over `num_bases` bases of `G` should run on: the external backend registered
+/// for `G` if the `gpu-msm` feature is enabled and it supports the MSM, otherwise [`CpuMsm`].
+pub fn msm_backend<G: CurveGroup>(num_bases: usize) -> &'static dyn MsmBackend<G> {
+  #[cfg(feature = "gpu-msm")]
+  {
+    let curve = std::any::TypeId::of::<G>();
+    let backends = EXTERNAL_BACKENDS.read().unwrap();
+    let registered = backends
+      .iter()
+      .find(|(registered, _)| *registered == curve)
+      .and_then(|(_, backend)| backend.downcast_ref::<&'static dyn MsmBackend<G>>());
+    if let Some(backend) = registered {
+      if backend.supports(num_bases) {
+        return *backend;
+      }
+    }
+  }
+  #[cfg(not(feature = "gpu-msm"))]
+  let _ = num_bases;
+
+  &CpuMsm
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+  use ark_std::{test_rng, UniformRand};
+
+  use super::*;
+
+  #[test]
+  fn cpu_backend_by_default() {
+    // Small MSMs run on the CPU backend unless a backend claiming them is registered
+    let mut rng = test_rng();
+    let bases: Vec<G1Projective> = (0..64).map(|_| G1Projective::rand(&mut rng)).collect();
+    let scalars: Vec<Fr> = (0..64).map(|_| Fr::rand(&mut rng)).collect();
+
+    let backend = msm_backend::<G1Projective>(bases.len());
+    assert_eq!(backend.name(), "cpu");
+
+    let expected: G1Projective = bases.iter().zip(scalars.iter()).map(|(b, s)| *b * s).sum();
+    let bases = G1Projective::normalize_batch(&bases);
+    assert_eq!(backend.msm(&bases, &scalars), expected);
+  }
+
+  /// Delegates to the CPU, but only claims MSMs of at least 2^20 bases so that registering it does
+  /// not affect other tests.
+  #[cfg(feature = "gpu-msm")]
+  struct LargeOnlyMsm;
+
+  #[cfg(feature = "gpu-msm")]
+  impl MsmBackend<G1Projective> for LargeOnlyMsm {
+    fn name(&self) -> &'static str {
+      "large-only"
+    }
+
+    fn supports(&self, num_bases: usize) -> bool {
+      num_bases >= 1 << 20
+    }
+
+    fn msm(&self, bases: &[<G1Projective as CurveGroup>::Affine], scalars: &[Fr]) -> G1Projective {
+      MsmBackend::<G1Projective>::msm(&CpuMsm, bases, scalars)
+    }
+  }
+
+  #[cfg(feature = "gpu-msm")]
+  #[test]
+  fn registered_backend_by_size() {
+    register_msm_backend::<G1Projective>(&LargeOnlyMsm);
+    // Registering again replaces the entry for the curve rather than adding one
+    register_msm_backend::<G1Projective>(&LargeOnlyMsm);
+    assert_eq!(EXTERNAL_BACKENDS.read().unwrap().len(), 1);
+
+    assert_eq!(msm_backend::<G1Projective>(64).name(), "cpu");
+    assert_eq!(msm_backend::<G1Projective>(1 << 20).name(), "large-only");
+  }
+}
@@ -8,6 +8,8 @@ use ark_ec::{CurveGroup, ScalarMul};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+pub mod backend;
+
 #[cfg(not(feature = "ark-msm"))]
 impl<G: CurveGroup> VariableBaseMSM for G {}
 
@@ -44,13 +46,95 @@ pub trait VariableBaseMSM: ScalarMul {
     bases: &[Self::MulBase],
     bigints: &[<Self::ScalarField as PrimeField>::BigInt],
   ) -> Self {
+    Self::msm_bigint_with_bits(bases, bigints, max_num_bits::<Self>(bigints))
+  }
+
+  /// Multi-scalar multiplication for scalars known to fit in `max_bits` bits, such as lookup
+  /// indices and timestamps. Only processes the windows covering the low `max_bits` bits.
+  ///
+  /// Panics if a scalar does not fit in `max_bits` bits, since the result would silently be wrong.
+  fn msm_small(bases: &[Self::MulBase], scalars: &[Self::ScalarField], max_bits: usize) -> Self {
+    assert!(max_bits > 0 && max_bits <= Self::ScalarField::MODULUS_BIT_SIZE as usize);
+    let bigints = ark_std::cfg_into_iter!(scalars)
+      .map(|s| s.into_bigint())
+      .collect::<Vec<_>>();
+    assert!(
+      bigints.iter().all(|b| b.num_bits() as usize <= max_bits),
+      "scalar exceeds {max_bits} bits"
+    );
+    Self::msm_bigint_with_bits(bases, &bigints, max_bits)
+  }
+
+  /// Multi-scalar multiplication over scalars of at most `num_bits` bits.
+  fn msm_bigint_with_bits(
+    bases: &[Self::MulBase],
+    bigints: &[<Self::ScalarField as PrimeField>::BigInt],
+    num_bits: usize,
+  ) -> Self {
+    let c = optimal_window_size(bases.len().min(bigints.len()));
     if Self::NEGATION_IS_CHEAP {
-      msm_bigint_wnaf(bases, bigints)
+      msm_bigint_wnaf(bases, bigints, num_bits, c, true)
     } else {
-      msm_bigint(bases, bigints)
+      msm_bigint(bases, bigints, num_bits, c)
     }
   }
 
+  /// Multi-scalar multiplication on the calling thread only, for use from contexts that are
+  /// already parallel (e.g. one MSM per memory) where nested parallelism would oversubscribe.
+  fn msm_serial(bases: &[Self::MulBase], scalars: &[Self::ScalarField]) -> Self {
+    let bigints = scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>();
+    let num_bits = max_num_bits::<Self>(&bigints);
+    let c = optimal_window_size(bases.len().min(bigints.len()));
+    if Self::NEGATION_IS_CHEAP {
+      msm_bigint_wnaf(bases, &bigints, num_bits, c, false)
+    } else {
+      msm_bigint(bases, &bigints, num_bits, c)
+    }
+  }
+
+  /// Multi-scalar multiplication using at most `threads` threads. The pool for each thread count is
+  /// built once and reused. `threads <= 1`, or failing to spawn the pool, falls back to
+  /// [`VariableBaseMSM::msm_serial`]. Calls to [`VariableBaseMSM::msm`] run on the global rayon
+  /// pool, whose size can be capped with `RAYON_NUM_THREADS`.
+  fn msm_with_threads(
+    bases: &[Self::MulBase],
+    scalars: &[Self::ScalarField],
+    threads: usize,
+  ) -> Self {
+    if threads <= 1 {
+      return Self::msm_serial(bases, scalars);
+    }
+
+    #[cfg(feature = "multicore")]
+    {
+      match thread_pool(threads) {
+        Some(pool) => pool.install(|| Self::msm_unchecked(bases, scalars)),
+        None => Self::msm_serial(bases, scalars),
+      }
+    }
+
+    #[cfg(not(feature = "multicore"))]
+    Self::msm_unchecked(bases, scalars)
+  }
+
+  /// Performs one multi-scalar multiplication per entry of `scalar_sets`, all against the same
+  /// `bases`. Equivalent to calling [`VariableBaseMSM::msm`] on each set, but the bucket arrays
+  /// are allocated once per window and reused across sets.
+  ///
+  /// Each scalar set may be shorter than `bases`, in which case only its prefix of `bases` is used.
+  fn batch_msm(bases: &[Self::MulBase], scalar_sets: &[&[Self::ScalarField]]) -> Vec<Self> {
+    let bigint_sets = scalar_sets
+      .iter()
+      .map(|scalars| {
+        assert!(scalars.len() <= bases.len());
+        ark_std::cfg_iter!(scalars)
+          .map(|s| s.into_bigint())
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    batch_msm_bigint(bases, &bigint_sets)
+  }
+
   /// Streaming multi-scalar multiplication algorithm with hard-coded chunk
   /// size.
   fn msm_chunks<I: ?Sized, J>(bases_stream: &J, scalars_stream: &I) -> Self
@@ -87,11 +171,31 @@ pub trait VariableBaseMSM: ScalarMul {
   }
 }
 
-// Compute msm using windowed non-adjacent form
-fn msm_bigint_wnaf<V: VariableBaseMSM>(
-  bases: &[V::MulBase],
-  bigints: &[<V::ScalarField as PrimeField>::BigInt],
-) -> V {
+/// Thread pools used by [`VariableBaseMSM::msm_with_threads`], keyed by thread count.
+#[cfg(feature = "multicore")]
+static THREAD_POOLS: std::sync::Mutex<Vec<(usize, std::sync::Arc<rayon::ThreadPool>)>> =
+  std::sync::Mutex::new(Vec::new());
+
+/// A pool of `threads` threads, built on first use and reused afterwards. `None` if the threads
+/// could not be spawned.
+#[cfg(feature = "multicore")]
+fn thread_pool(threads: usize) -> Option<std::sync::Arc<rayon::ThreadPool>> {
+  let mut pools = THREAD_POOLS.lock().unwrap();
+  if let Some((_, pool)) = pools.iter().find(|(n, _)| *n == threads) {
+    return Some(pool.clone());
+  }
+  let pool = std::sync::Arc::new(
+    rayon::ThreadPoolBuilder::new()
+      .num_threads(threads)
+      .build()
+      .ok()?,
+  );
+  pools.push((threads, pool.clone()));
+  Some(pool)
+}
+
+/// Bit size of the largest scalar, or the full modulus size as soon as any scalar exceeds 60 bits.
+fn max_num_bits<V: VariableBaseMSM>(bigints: &[<V::ScalarField as PrimeField>::BigInt]) -> usize {
   let mut max_num_bits = 1usize;
   for bigint in bigints {
     if bigint.num_bits() as usize > max_num_bits {
@@ -104,47 +208,56 @@ fn msm_bigint_wnaf<V: VariableBaseMSM>(
       break;
     }
   }
+  max_num_bits
+}
 
+// Compute msm using windowed non-adjacent form with `c`-bit windows. Windows are processed in
+// parallel unless `parallel` is false.
+fn msm_bigint_wnaf<V: VariableBaseMSM>(
+  bases: &[V::MulBase],
+  bigints: &[<V::ScalarField as PrimeField>::BigInt],
+  num_bits: usize,
+  c: usize,
+  parallel: bool,
+) -> V {
   let size = ark_std::cmp::min(bases.len(), bigints.len());
   let scalars = &bigints[..size];
   let bases = &bases[..size];
 
-  let c = if size < 32 {
-    3
-  } else {
-    ln_without_floats(size) + 2
-  };
-
-  let num_bits = max_num_bits;
   let digits_count = (num_bits + c - 1) / c;
   let scalar_digits = scalars
     .iter()
     .flat_map(|s| make_digits(s, c, num_bits))
     .collect::<Vec<_>>();
   let zero = V::zero();
-  let window_sums: Vec<_> = ark_std::cfg_into_iter!(0..digits_count)
-    .map(|i| {
-      let mut buckets = vec![zero; 1 << c];
-      for (digits, base) in scalar_digits.chunks(digits_count).zip(bases) {
-        use ark_std::cmp::Ordering;
-        // digits is the digits thing of the first scalar?
-        let scalar = digits[i];
-        match 0.cmp(&scalar) {
-          Ordering::Less => buckets[(scalar - 1) as usize] += base,
-          Ordering::Greater => buckets[(-scalar - 1) as usize] -= base,
-          Ordering::Equal => (),
-        }
+  let window_sum = |i: usize| {
+    let mut buckets = vec![zero; 1 << c];
+    for (digits, base) in scalar_digits.chunks(digits_count).zip(bases) {
+      use ark_std::cmp::Ordering;
+      // digits is the digits thing of the first scalar?
+      let scalar = digits[i];
+      match 0.cmp(&scalar) {
+        Ordering::Less => buckets[(scalar - 1) as usize] += base,
+        Ordering::Greater => buckets[(-scalar - 1) as usize] -= base,
+        Ordering::Equal => (),
       }
+    }
 
-      let mut running_sum = V::zero();
-      let mut res = V::zero();
-      buckets.into_iter().rev().for_each(|b| {
-        running_sum += &b;
-        res += &running_sum;
-      });
-      res
-    })
-    .collect();
+    let mut running_sum = V::zero();
+    let mut res = V::zero();
+    buckets.into_iter().rev().for_each(|b| {
+      running_sum += &b;
+      res += &running_sum;
+    });
+    res
+  };
+  let window_sums: Vec<_> = if parallel {
+    ark_std::cfg_into_iter!(0..digits_count)
+      .map(window_sum)
+      .collect()
+  } else {
+    (0..digits_count).map(window_sum).collect()
+  };
 
   // We store the sum for the lowest window.
   let lowest = *window_sums.first().unwrap();
@@ -163,36 +276,18 @@ fn msm_bigint_wnaf<V: VariableBaseMSM>(
       })
 }
 
-/// Optimized implementation of multi-scalar multiplication.
+/// Optimized implementation of multi-scalar multiplication with `c`-bit windows.
 fn msm_bigint<V: VariableBaseMSM>(
   bases: &[V::MulBase],
   bigints: &[<V::ScalarField as PrimeField>::BigInt],
+  num_bits: usize,
+  c: usize,
 ) -> V {
   let size = ark_std::cmp::min(bases.len(), bigints.len());
   let scalars = &bigints[..size];
   let bases = &bases[..size];
   let scalars_and_bases_iter = scalars.iter().zip(bases).filter(|(s, _)| !s.is_zero());
 
-  let c = if size < 32 {
-    3
-  } else {
-    ln_without_floats(size) + 2
-  };
-
-  let mut max_num_bits = 1usize;
-  for bigint in bigints {
-    if bigint.num_bits() as usize > max_num_bits {
-      max_num_bits = bigint.num_bits() as usize;
-    }
-
-    // Hack
-    if max_num_bits > 60 {
-      max_num_bits = V::ScalarField::MODULUS_BIT_SIZE as usize;
-      break;
-    }
-  }
-
-  let num_bits = max_num_bits;
   let one = V::ScalarField::one().into_bigint();
 
   let zero = V::zero();
@@ -273,6 +368,103 @@ fn msm_bigint<V: VariableBaseMSM>(
       })
 }
 
+/// Pippenger window size (bits per bucket window) for an MSM over `n` bases: 3 below 32 bases,
+/// otherwise roughly `ln(n) + 2`. Non-decreasing in `n`.
+pub fn optimal_window_size(n: usize) -> usize {
+  if n < 32 {
+    3
+  } else {
+    ln_without_floats(n) + 2
+  }
+}
+
+/// The result of this function is only approximately `ln(a)`
+/// [`Explanation of usage`]
+///
+/// [`Explanation of usage`]: https://github.com/scipr-lab/zexe/issues/79#issue-556220473
+fn ln_without_floats(a: usize) -> usize {
+  // log2(a) * ln(2)
+  (ark_std::log2(a) * 69 / 100) as usize
+}
+
+/// Multi-scalar multiplication with a fixed `c`-bit window instead of [`optimal_window_size`],
+/// for comparing window sizes.
+pub(crate) fn msm_with_window<V: VariableBaseMSM>(
+  bases: &[V::MulBase],
+  scalars: &[V::ScalarField],
+  c: usize,
+) -> V {
+  let bigints = scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>();
+  let num_bits = max_num_bits::<V>(&bigints);
+  if V::NEGATION_IS_CHEAP {
+    msm_bigint_wnaf(bases, &bigints, num_bits, c, true)
+  } else {
+    msm_bigint(bases, &bigints, num_bits, c)
+  }
+}
+
+/// Pippenger over several scalar sets sharing the same bases. Windows are processed in parallel;
+/// within a window a single bucket array is cleared and reused for every set.
+fn batch_msm_bigint<V: VariableBaseMSM>(
+  bases: &[V::MulBase],
+  bigint_sets: &[Vec<<V::ScalarField as PrimeField>::BigInt>],
+) -> Vec<V> {
+  let size = bigint_sets.iter().map(Vec::len).max().unwrap_or(0);
+  let c = optimal_window_size(size);
+
+  let num_bits = bigint_sets
+    .iter()
+    .flatten()
+    .map(|bigint| bigint.num_bits() as usize)
+    .max()
+    .unwrap_or(0)
+    .max(1);
+
+  let zero = V::zero();
+
+  // window_sums[w][i] is the sum of window `w` for scalar set `i`.
+  let window_sums: Vec<Vec<V>> = ark_std::cfg_into_iter!(0..(num_bits + c - 1) / c)
+    .map(|w| {
+      let w_start = w * c;
+      let mut buckets = vec![zero; (1 << c) - 1];
+      bigint_sets
+        .iter()
+        .map(|bigints| {
+          buckets.iter_mut().for_each(|b| *b = zero);
+          for (scalar, base) in bigints.iter().zip(bases) {
+            let mut scalar = *scalar;
+            scalar.divn(w_start as u32);
+            let digit = scalar.as_ref()[0] % (1 << c);
+            if digit != 0 {
+              buckets[(digit - 1) as usize] += base;
+            }
+          }
+
+          let mut running_sum = zero;
+          let mut res = zero;
+          buckets.iter().rev().for_each(|b| {
+            running_sum += b;
+            res += &running_sum;
+          });
+          res
+        })
+        .collect()
+    })
+    .collect();
+
+  // Traverse windows from high to low for each set.
+  (0..bigint_sets.len())
+    .map(|i| {
+      window_sums.iter().rev().fold(zero, |mut total, sums| {
+        for _ in 0..c {
+          total.double_in_place();
+        }
+        total + sums[i]
+      })
+    })
+    .collect()
+}
+
 // From: https://github.com/arkworks-rs/gemini/blob/main/src/kzg/msm/variable_base.rs#L20
 fn make_digits(a: &impl BigInteger, w: usize, num_bits: usize) -> Vec<i64> {
   let scalar = a.as_ref();
@@ -315,11 +507,109 @@ fn make_digits(a: &impl BigInteger, w: usize, num_bits: usize) -> Vec<i64> {
   digits
 }
 
-/// The result of this function is only approximately `ln(a)`
-/// [`Explanation of usage`]
-///
-/// [`Explanation of usage`]: https://github.com/scipr-lab/zexe/issues/79#issue-556220473
-fn ln_without_floats(a: usize) -> usize {
-  // log2(a) * ln(2)
-  (ark_std::log2(a) * 69 / 100) as usize
+#[cfg(test)]
+mod test {
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+  use ark_std::{test_rng, UniformRand};
+  use rand_chacha::rand_core::RngCore;
+
+  use super::*;
+
+  #[test]
+  fn batch_msm_matches_msm() {
+    let mut rng = test_rng();
+    let n = 1 << 8;
+    let bases: Vec<_> = (0..n)
+      .map(|_| G1Projective::rand(&mut rng).into_affine())
+      .collect();
+    let mut scalar_sets: Vec<Vec<Fr>> = (0..4)
+      .map(|_| (0..n).map(|_| Fr::rand(&mut rng)).collect())
+      .collect();
+    // small scalars, a shorter set and an all-zero set
+    scalar_sets.push((0..n as u64).map(Fr::from).collect());
+    scalar_sets.push((0..n / 2).map(|_| Fr::rand(&mut rng)).collect());
+    scalar_sets.push(vec![Fr::zero(); n]);
+
+    let scalar_refs: Vec<&[Fr]> = scalar_sets.iter().map(Vec::as_slice).collect();
+    let batched = G1Projective::batch_msm(&bases, &scalar_refs);
+    for (scalars, result) in scalar_sets.iter().zip(batched) {
+      let expected = G1Projective::msm(&bases[..scalars.len()], scalars).unwrap();
+      assert_eq!(result, expected);
+    }
+  }
+
+  #[test]
+  fn msm_small_matches_msm() {
+    let mut rng = test_rng();
+    let n = 1 << 8;
+    let bases: Vec<_> = (0..n)
+      .map(|_| G1Projective::rand(&mut rng).into_affine())
+      .collect();
+    let scalars: Vec<Fr> = (0..n)
+      .map(|_| Fr::from(rng.next_u64() % (1 << 16)))
+      .collect();
+
+    let expected = G1Projective::msm(&bases, &scalars).unwrap();
+    assert_eq!(G1Projective::msm_small(&bases, &scalars, 16), expected);
+    // A loose bound is still correct
+    assert_eq!(G1Projective::msm_small(&bases, &scalars, 40), expected);
+  }
+
+  #[test]
+  #[should_panic(expected = "scalar exceeds 16 bits")]
+  fn msm_small_rejects_wide_scalars() {
+    let mut rng = test_rng();
+    let bases: Vec<_> = (0..4)
+      .map(|_| G1Projective::rand(&mut rng).into_affine())
+      .collect();
+    let scalars = [
+      Fr::from(1u64),
+      Fr::from(1u64 << 16),
+      Fr::from(2u64),
+      Fr::from(3u64),
+    ];
+    G1Projective::msm_small(&bases, &scalars, 16);
+  }
+
+  #[test]
+  fn serial_and_parallel_msm_match() {
+    let mut rng = test_rng();
+    let n = 1 << 10;
+    let bases: Vec<_> = (0..n)
+      .map(|_| G1Projective::rand(&mut rng).into_affine())
+      .collect();
+    let scalars: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+
+    let parallel = G1Projective::msm(&bases, &scalars).unwrap();
+    assert_eq!(G1Projective::msm_serial(&bases, &scalars), parallel);
+    assert_eq!(
+      G1Projective::msm_with_threads(&bases, &scalars, 1),
+      parallel
+    );
+    // The second call reuses the cached 2-thread pool
+    for _ in 0..2 {
+      assert_eq!(
+        G1Projective::msm_with_threads(&bases, &scalars, 2),
+        parallel
+      );
+    }
+  }
+
+  #[test]
+  fn window_size_non_decreasing() {
+    let mut prev = optimal_window_size(1);
+    for n in 2..(1 << 12) {
+      let c = optimal_window_size(n);
+      assert!(c >= prev, "window shrank from {prev} to {c} at n = {n}");
+      prev = c;
+    }
+    for log_n in 12..32 {
+      let c = optimal_window_size(1 << log_n);
+      assert!(
+        c >= prev,
+        "window shrank from {prev} to {c} at n = 2^{log_n}"
+      );
+      prev = c;
+    }
+  }
 }
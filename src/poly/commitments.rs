@@ -5,11 +5,7 @@ use rand_chacha::ChaCha20Rng;
 use sha3::Shake256;
 use std::io::Read;
 
-#[cfg(feature = "ark-msm")]
-use ark_ec::VariableBaseMSM;
-
-#[cfg(not(feature = "ark-msm"))]
-use crate::msm::VariableBaseMSM;
+use crate::msm::backend::msm_backend;
 
 #[derive(Debug)]
 pub struct MultiCommitGens<G> {
@@ -72,6 +68,21 @@ impl<G: CurveGroup> MultiCommitGens<G> {
 pub trait Commitments<G: CurveGroup>: Sized {
   fn commit(&self, blind: &G::ScalarField, gens_n: &MultiCommitGens<G>) -> G;
   fn batch_commit(inputs: &[Self], blind: &G::ScalarField, gens_n: &MultiCommitGens<G>) -> G;
+  /// Same as `batch_commit`, for inputs known to fit in `max_bits` bits.
+  fn batch_commit_small(
+    inputs: &[Self],
+    blind: &G::ScalarField,
+    max_bits: usize,
+    gens_n: &MultiCommitGens<G>,
+  ) -> G;
+  /// Updates `old_commitment` to also commit to `appended` at positions `offset..offset + appended.len()`,
+  /// which must have been zero in the committed vector. The blind is unchanged.
+  fn update_commitment(
+    old_commitment: &G,
+    appended: &[Self],
+    offset: usize,
+    gens_n: &MultiCommitGens<G>,
+  ) -> G;
 }
 
 impl<G: CurveGroup> Commitments<G> for G::ScalarField {
@@ -89,6 +100,74 @@ impl<G: CurveGroup> Commitments<G> for G::ScalarField {
     bases.push(gens_n.h.into_affine());
     scalars.push(*blind);
 
-    VariableBaseMSM::msm(bases.as_ref(), scalars.as_ref()).unwrap()
+    msm_backend::<G>(bases.len()).msm(bases.as_ref(), scalars.as_ref())
+  }
+
+  fn batch_commit_small(
+    inputs: &[Self],
+    blind: &G::ScalarField,
+    max_bits: usize,
+    gens_n: &MultiCommitGens<G>,
+  ) -> G {
+    assert_eq!(gens_n.n, inputs.len());
+
+    // The blind is full width, so it is added outside the small MSM
+    let bases = CurveGroup::normalize_batch(gens_n.G.as_ref());
+    msm_backend::<G>(bases.len()).msm_small(bases.as_ref(), inputs, max_bits) + gens_n.h * blind
+  }
+
+  fn update_commitment(
+    old_commitment: &G,
+    appended: &[Self],
+    offset: usize,
+    gens_n: &MultiCommitGens<G>,
+  ) -> G {
+    assert!(offset + appended.len() <= gens_n.n);
+    if appended.is_empty() {
+      return *old_commitment;
+    }
+
+    let bases = CurveGroup::normalize_batch(&gens_n.G[offset..offset + appended.len()]);
+    *old_commitment + msm_backend::<G>(bases.len()).msm(bases.as_ref(), appended)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+  use ark_std::{test_rng, UniformRand, Zero};
+
+  use super::*;
+
+  #[test]
+  fn update_commitment_matches_full_commit() {
+    let mut rng = test_rng();
+    let gens = MultiCommitGens::<G1Projective>::new(4, b"test_gens");
+    let blind = Fr::rand(&mut rng);
+    let [a, b, c, d] = [(); 4].map(|_| Fr::rand(&mut rng));
+
+    let prefix = Commitments::batch_commit(&[a, b, Fr::zero(), Fr::zero()], &blind, &gens);
+    let updated = <Fr as Commitments<G1Projective>>::update_commitment(&prefix, &[c, d], 2, &gens);
+    assert_eq!(
+      updated,
+      Commitments::batch_commit(&[a, b, c, d], &blind, &gens)
+    );
+
+    // Appending nothing leaves the commitment unchanged
+    let unchanged = <Fr as Commitments<G1Projective>>::update_commitment(&prefix, &[], 4, &gens);
+    assert_eq!(unchanged, prefix);
+  }
+
+  #[test]
+  fn batch_commit_small_matches_batch_commit() {
+    let mut rng = test_rng();
+    let gens = MultiCommitGens::<G1Projective>::new(16, b"test_gens");
+    let blind = Fr::rand(&mut rng);
+    let inputs: Vec<Fr> = (0..16u64).map(|i| Fr::from(i * 7 % 16)).collect();
+
+    assert_eq!(
+      <Fr as Commitments<G1Projective>>::batch_commit_small(&inputs, &blind, 4, &gens),
+      Commitments::batch_commit(&inputs, &blind, &gens)
+    );
   }
 }
@@ -13,13 +13,8 @@ use ark_ff::PrimeField;
 use ark_serialize::*;
 use ark_std::Zero;
 use core::ops::Index;
-use merlin::Transcript;
 
-#[cfg(feature = "ark-msm")]
-use ark_ec::VariableBaseMSM;
-
-#[cfg(not(feature = "ark-msm"))]
-use crate::msm::VariableBaseMSM;
+use crate::msm::backend::msm_backend;
 
 #[cfg(feature = "multicore")]
 use rayon::prelude::*;
@@ -110,6 +105,7 @@ impl<F: PrimeField> DensePolynomial<F> {
   fn commit_inner<G: CurveGroup<ScalarField = F>>(
     &self,
     blinds: &[F],
+    max_bits: Option<usize>,
     gens: &MultiCommitGens<G>,
   ) -> PolyCommitment<G> {
     let L_size = blinds.len();
@@ -118,11 +114,11 @@ impl<F: PrimeField> DensePolynomial<F> {
     let C = (0..L_size)
       .into_par_iter()
       .map(|i| {
-        Commitments::batch_commit(
-          self.Z[R_size * i..R_size * (i + 1)].as_ref(),
-          &blinds[i],
-          gens,
-        )
+        let row = self.Z[R_size * i..R_size * (i + 1)].as_ref();
+        match max_bits {
+          Some(max_bits) => Commitments::batch_commit_small(row, &blinds[i], max_bits, gens),
+          None => Commitments::batch_commit(row, &blinds[i], gens),
+        }
       })
       .collect();
     PolyCommitment { C }
@@ -132,6 +128,7 @@ impl<F: PrimeField> DensePolynomial<F> {
   fn commit_inner<G: CurveGroup<ScalarField = F>>(
     &self,
     blinds: &[F],
+    max_bits: Option<usize>,
     gens: &MultiCommitGens<G>,
   ) -> PolyCommitment<G> {
     let L_size = blinds.len();
@@ -139,22 +136,48 @@ impl<F: PrimeField> DensePolynomial<F> {
     assert_eq!(L_size * R_size, self.Z.len());
     let C = (0..L_size)
       .map(|i| {
-        Commitments::batch_commit(
-          self.Z[R_size * i..R_size * (i + 1)].as_ref(),
-          &blinds[i],
-          gens,
-        )
+        let row = self.Z[R_size * i..R_size * (i + 1)].as_ref();
+        match max_bits {
+          Some(max_bits) => Commitments::batch_commit_small(row, &blinds[i], max_bits, gens),
+          None => Commitments::batch_commit(row, &blinds[i], gens),
+        }
       })
       .collect();
     PolyCommitment { C }
   }
 
-  #[tracing::instrument(skip_all, name = "DensePolynomial.commit")]
   pub fn commit<G>(
     &self,
     gens: &PolyCommitmentGens<G>,
     random_tape: Option<&mut RandomTape<G>>,
   ) -> (PolyCommitment<G>, PolyCommitmentBlinds<F>)
+  where
+    G: CurveGroup<ScalarField = F>,
+  {
+    self.commit_with_max_bits(gens, random_tape, None)
+  }
+
+  /// Same as `commit`, for evaluations known to fit in `max_bits` bits (e.g. lookup indices and
+  /// timestamps), whose MSMs skip the windows above `max_bits`.
+  pub fn commit_small<G>(
+    &self,
+    gens: &PolyCommitmentGens<G>,
+    random_tape: Option<&mut RandomTape<G>>,
+    max_bits: usize,
+  ) -> (PolyCommitment<G>, PolyCommitmentBlinds<F>)
+  where
+    G: CurveGroup<ScalarField = F>,
+  {
+    self.commit_with_max_bits(gens, random_tape, Some(max_bits))
+  }
+
+  #[tracing::instrument(skip_all, name = "DensePolynomial.commit")]
+  fn commit_with_max_bits<G>(
+    &self,
+    gens: &PolyCommitmentGens<G>,
+    random_tape: Option<&mut RandomTape<G>>,
+    max_bits: Option<usize>,
+  ) -> (PolyCommitment<G>, PolyCommitmentBlinds<F>)
   where
     G: CurveGroup<ScalarField = F>,
   {
@@ -177,7 +200,10 @@ impl<F: PrimeField> DensePolynomial<F> {
       }
     };
 
-    (self.commit_inner(&blinds.blinds, &gens.gens.gens_n), blinds)
+    (
+      self.commit_inner(&blinds.blinds, max_bits, &gens.gens.gens_n),
+      blinds,
+    )
   }
 
   #[tracing::instrument(skip_all, name = "DensePolynomial.bound")]
@@ -234,6 +260,46 @@ impl<F: PrimeField> DensePolynomial<F> {
     compute_dotproduct(&self.Z, &chis)
   }
 
+  /// Like `bound_poly_var_top`, but leaves `self` untouched and returns the bound polynomial in a
+  /// freshly allocated vector of half the length.
+  fn bound_top(&self, r: &F) -> DensePolynomial<F> {
+    let n = self.len() / 2;
+    DensePolynomial::new(
+      (0..n)
+        .map(|i| self.Z[i] + *r * (self.Z[i + n] - self.Z[i]))
+        .collect(),
+    )
+  }
+
+  /// Evaluates the polynomial at each of `points`. Variables are bound top-down and the partially
+  /// bound polynomials are kept on a stack, so consecutive points sharing a prefix only pay for
+  /// binding the variables after it. Binding every variable of a point takes about `len()`
+  /// multiplications, so points with no shared prefix cost no more than `evaluate`.
+  pub fn evaluate_many(&self, points: &[Vec<F>]) -> Vec<F> {
+    // stack[k] has the first k + 1 variables bound to the current point's prefix
+    let mut stack: Vec<DensePolynomial<F>> = Vec::with_capacity(self.get_num_vars());
+    let mut prev: &[F] = &[];
+
+    points
+      .iter()
+      .map(|r| {
+        assert_eq!(r.len(), self.get_num_vars());
+        let shared = prev
+          .iter()
+          .zip(r.iter())
+          .take_while(|(a, b)| a == b)
+          .count();
+        stack.truncate(shared);
+        for r_i in r[shared..].iter() {
+          let bound = stack.last().unwrap_or(self).bound_top(r_i);
+          stack.push(bound);
+        }
+        prev = r;
+        stack.last().unwrap_or(self)[0]
+      })
+      .collect()
+  }
+
   fn vec(&self) -> &Vec<F> {
     &self.Z
   }
@@ -260,6 +326,22 @@ impl<F: PrimeField> DensePolynomial<F> {
     DensePolynomial::new(Z)
   }
 
+  /// Like `merge`, but also returns the layout needed to recover the individual polynomials.
+  pub fn merge_with_layout(polys: &[DensePolynomial<F>]) -> (DensePolynomial<F>, MergeLayout) {
+    let layout = MergeLayout::new(&polys.iter().map(|poly| poly.len()).collect::<Vec<usize>>());
+    (Self::merge(polys), layout)
+  }
+
+  /// Inverse of `merge`: extracts the polynomials described by `layout`, dropping the padding.
+  pub fn split_merged(&self, layout: &MergeLayout) -> Vec<DensePolynomial<F>> {
+    assert!(layout.total_len() <= self.len());
+    layout
+      .segments
+      .iter()
+      .map(|(offset, len)| DensePolynomial::new(self.Z[*offset..*offset + *len].to_vec()))
+      .collect()
+  }
+
   pub fn from_usize(Z: &[usize]) -> Self {
     DensePolynomial::new(
       (0..Z.len())
@@ -267,6 +349,60 @@ impl<F: PrimeField> DensePolynomial<F> {
         .collect::<Vec<F>>(),
     )
   }
+
+  /// Builds a polynomial from small (e.g. trace or timestamp) evaluations, converting them one
+  /// by one (in parallel under `multicore`).
+  pub fn from_u64_evals(Z: &[u64]) -> Self {
+    #[cfg(feature = "multicore")]
+    let evals = Z.par_iter().map(|z| F::from(*z)).collect();
+
+    #[cfg(not(feature = "multicore"))]
+    let evals = Z.iter().map(|z| F::from(*z)).collect();
+
+    DensePolynomial::new(evals)
+  }
+}
+
+/// Offsets and lengths of the polynomials concatenated by `DensePolynomial::merge`, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeLayout {
+  segments: Vec<(usize, usize)>,
+}
+
+impl MergeLayout {
+  pub fn new(lens: &[usize]) -> Self {
+    let mut offset = 0;
+    let segments = lens
+      .iter()
+      .map(|len| {
+        let segment = (offset, *len);
+        offset += len;
+        segment
+      })
+      .collect();
+    MergeLayout { segments }
+  }
+
+  pub fn num_polys(&self) -> usize {
+    self.segments.len()
+  }
+
+  /// (offset, length) of the `index`-th polynomial within the merged polynomial.
+  pub fn segment(&self, index: usize) -> (usize, usize) {
+    self.segments[index]
+  }
+
+  /// Index of the polynomial containing the merged evaluation at `position`, if any.
+  pub fn find(&self, position: usize) -> Option<usize> {
+    self
+      .segments
+      .iter()
+      .position(|(offset, len)| (*offset..*offset + *len).contains(&position))
+  }
+
+  fn total_len(&self) -> usize {
+    self.segments.iter().map(|(_, len)| len).sum()
+  }
 }
 
 impl<F> Index<usize> for DensePolynomial<F> {
@@ -299,17 +435,17 @@ impl<G: CurveGroup> PolyEvalProof<G> {
   }
 
   #[tracing::instrument(skip_all, name = "DensePolyEval.prove")]
-  pub fn prove(
+  pub fn prove<T: ProofTranscript<G>>(
     poly: &DensePolynomial<G::ScalarField>,
     blinds_opt: Option<&PolyCommitmentBlinds<G::ScalarField>>,
     r: &[G::ScalarField], // point at which the polynomial is evaluated
     Zr: &G::ScalarField,  // evaluation of \widetilde{Z}(r)
     blind_Zr_opt: Option<&G::ScalarField>, // specifies a blind for Zr
     gens: &PolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
   ) -> (PolyEvalProof<G>, G) {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       PolyEvalProof::<G>::protocol_name(),
     );
@@ -358,15 +494,15 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     (PolyEvalProof { proof }, C_Zr_prime)
   }
 
-  pub fn verify(
+  pub fn verify<T: ProofTranscript<G>>(
     &self,
     gens: &PolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     r: &[G::ScalarField], // point at which the polynomial is evaluated
     C_Zr: &G,             // commitment to \widetilde{Z}(r)
     comm: &PolyCommitment<G>,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       PolyEvalProof::<G>::protocol_name(),
     );
@@ -376,19 +512,22 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     let (L, R) = eq.compute_factored_evals();
 
     // compute a weighted sum of commitments and L
+    if comm.C.len() != L.len() {
+      return Err(ProofVerifyError::InvalidInputLength(L.len(), comm.C.len()));
+    }
     let C_affine = G::normalize_batch(&comm.C);
 
-    let C_LZ = VariableBaseMSM::msm(C_affine.as_ref(), L.as_ref()).unwrap();
+    let C_LZ = msm_backend::<G>(C_affine.len()).msm(C_affine.as_ref(), L.as_ref());
 
     self
       .proof
       .verify(R.len(), &gens.gens, transcript, &R, &C_LZ, C_Zr)
   }
 
-  pub fn verify_plain(
+  pub fn verify_plain<T: ProofTranscript<G>>(
     &self,
     gens: &PolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     r: &[G::ScalarField], // point at which the polynomial is evaluated
     Zr: &G::ScalarField,  // evaluation \widetilde{Z}(r)
     comm: &PolyCommitment<G>,
@@ -410,6 +549,8 @@ mod tests {
   use ark_std::test_rng;
   use ark_std::One;
   use ark_std::UniformRand;
+  use merlin::Transcript;
+  use rand_chacha::rand_core::RngCore;
 
   fn evaluate_with_LR<G: CurveGroup>(Z: &[G::ScalarField], r: &[G::ScalarField]) -> G::ScalarField {
     let eq = EqPolynomial::<G::ScalarField>::new(r.to_vec());
@@ -624,6 +765,115 @@ mod tests {
       .is_ok());
   }
 
+  #[test]
+  fn merge_split_round_trip() {
+    let mut rng = test_rng();
+    let polys: Vec<DensePolynomial<Fr>> = [4, 8, 2, 4]
+      .iter()
+      .map(|len| DensePolynomial::new((0..*len).map(|_| Fr::rand(&mut rng)).collect()))
+      .collect();
+
+    let (merged, layout) = DensePolynomial::merge_with_layout(&polys);
+    assert_eq!(merged.len(), 32);
+    assert_eq!(layout.num_polys(), 4);
+    assert_eq!(layout.segment(2), (12, 2));
+    assert_eq!(layout.find(13), Some(2));
+    assert_eq!(layout.find(20), None); // padding
+
+    let split = merged.split_merged(&layout);
+    for (original, recovered) in polys.iter().zip(split.iter()) {
+      assert_eq!(original.len(), recovered.len());
+      for i in 0..original.len() {
+        assert_eq!(original[i], recovered[i]);
+      }
+    }
+  }
+
+  #[test]
+  fn from_u64_evals() {
+    let mut rng = test_rng();
+    let evals: Vec<u64> = (0..1 << 10)
+      .map(|i| match i % 3 {
+        0 => i,
+        1 => rng.next_u64(),
+        _ => u64::MAX - i,
+      })
+      .collect();
+
+    let expected = DensePolynomial::new(evals.iter().map(|z| Fr::from(*z)).collect());
+    assert_eq!(DensePolynomial::<Fr>::from_u64_evals(&evals).Z, expected.Z);
+
+    let usize_evals: Vec<usize> = evals.iter().map(|z| *z as usize).collect();
+    assert_eq!(
+      DensePolynomial::<Fr>::from_usize(&usize_evals).Z,
+      expected.Z
+    );
+  }
+
+  #[test]
+  fn evaluate_many() {
+    let mut rng = test_rng();
+    let num_vars = 6;
+    let poly = DensePolynomial::new((0..1 << num_vars).map(|_| Fr::rand(&mut rng)).collect());
+
+    let base: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut rng)).collect();
+    let mut points: Vec<Vec<Fr>> = Vec::new();
+    for shared in [0, 3, 5, 6, 1] {
+      // share the first `shared` coordinates with `base`
+      let mut point = base.clone();
+      for r_i in point.iter_mut().skip(shared) {
+        *r_i = Fr::rand(&mut rng);
+      }
+      points.push(point);
+    }
+    points.push(base.clone());
+    points.push(base);
+
+    let naive: Vec<Fr> = points.iter().map(|r| poly.evaluate(r)).collect();
+    assert_eq!(poly.evaluate_many(&points), naive);
+  }
+
+  #[test]
+  fn binding_order() {
+    let mut rng = test_rng();
+    let num_vars = 5;
+    let Z: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::rand(&mut rng)).collect();
+    let r: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut rng)).collect();
+
+    // Brute-force multilinear extension: sum_x Z[x] * eq(x, r), with r[0] the most significant bit
+    let brute_force: Fr = (0..Z.len())
+      .map(|x| {
+        let eq: Fr = (0..num_vars)
+          .map(|i| {
+            if (x >> (num_vars - i - 1)) & 1 == 1 {
+              r[i]
+            } else {
+              Fr::one() - r[i]
+            }
+          })
+          .product();
+        Z[x] * eq
+      })
+      .sum();
+
+    let mut top_down = DensePolynomial::new(Z.clone());
+    for r_i in r.iter() {
+      top_down.bound_poly_var_top(r_i);
+    }
+
+    // Binding from the bottom fixes the least significant variable first
+    let mut bottom_up = DensePolynomial::new(Z.clone());
+    for r_i in r.iter().rev() {
+      bottom_up.bound_poly_var_bot(r_i);
+    }
+
+    assert_eq!(top_down.len(), 1);
+    assert_eq!(bottom_up.len(), 1);
+    assert_eq!(top_down[0], brute_force);
+    assert_eq!(bottom_up[0], brute_force);
+    assert_eq!(DensePolynomial::new(Z).evaluate(&r), brute_force);
+  }
+
   #[test]
   fn evaluation() {
     let num_evals = 4;
@@ -51,3 +51,32 @@ impl<F: PrimeField> EqPolynomial<F> {
     (L, R)
   }
 }
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+  use ark_std::{test_rng, UniformRand};
+
+  use super::*;
+  use crate::utils::index_to_field_bitvector;
+
+  #[test]
+  fn evals_match_evaluate() {
+    let mut rng = test_rng();
+    let ell = 5;
+    let r: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+    let eq = EqPolynomial::new(r);
+
+    let evals = eq.evals();
+    assert_eq!(evals.len(), ell.pow2());
+    for (idx, eval) in evals.iter().enumerate() {
+      assert_eq!(*eval, eq.evaluate(&index_to_field_bitvector(idx, ell)));
+    }
+
+    // The factored tables are the two halves of the full table
+    let (L, R) = eq.compute_factored_evals();
+    for (idx, eval) in evals.iter().enumerate() {
+      assert_eq!(*eval, L[idx / R.len()] * R[idx % R.len()]);
+    }
+  }
+}
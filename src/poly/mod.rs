@@ -2,4 +2,5 @@ pub mod commitments;
 pub mod dense_mlpoly;
 pub mod eq_poly;
 pub mod identity_poly;
+pub mod sparse_mlpoly;
 pub mod unipoly;
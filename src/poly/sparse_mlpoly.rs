@@ -0,0 +1,109 @@
+use ark_ff::PrimeField;
+
+use super::dense_mlpoly::DensePolynomial;
+
+/// Multilinear polynomial stored as its non-zero evaluations over the boolean hypercube,
+/// as (index, value) pairs. Indices use the same bit order as `DensePolynomial`: the first
+/// variable is the most significant bit.
+// Only exercised by its tests until memory checking builds sparse address polynomials with it.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct SparsePolynomial<F> {
+  num_vars: usize,
+  Z: Vec<(usize, F)>,
+}
+
+#[allow(dead_code)]
+impl<F: PrimeField> SparsePolynomial<F> {
+  pub fn new(num_vars: usize, Z: Vec<(usize, F)>) -> Self {
+    assert!(
+      Z.iter().all(|(index, _)| *index < 1 << num_vars),
+      "Sparse entry index out of range for {num_vars} variables"
+    );
+    SparsePolynomial { num_vars, Z }
+  }
+
+  pub fn get_num_vars(&self) -> usize {
+    self.num_vars
+  }
+
+  /// Number of stored (non-zero) entries.
+  pub fn num_entries(&self) -> usize {
+    self.Z.len()
+  }
+
+  /// eq(index, r), where `index` is interpreted as a point on the boolean hypercube.
+  fn compute_chi(index: usize, r: &[F]) -> F {
+    let num_vars = r.len();
+    (0..num_vars)
+      .map(|i| {
+        if (index >> (num_vars - i - 1)) & 1 == 1 {
+          r[i]
+        } else {
+          F::one() - r[i]
+        }
+      })
+      .product()
+  }
+
+  // returns Z(r) in O(num_entries * num_vars) time
+  pub fn evaluate(&self, r: &[F]) -> F {
+    assert_eq!(r.len(), self.num_vars);
+    self
+      .Z
+      .iter()
+      .map(|(index, value)| *value * Self::compute_chi(*index, r))
+      .sum()
+  }
+
+  pub fn to_dense(&self) -> DensePolynomial<F> {
+    let mut Z = vec![F::zero(); 1 << self.num_vars];
+    for (index, value) in self.Z.iter() {
+      Z[*index] += value;
+    }
+    DensePolynomial::new(Z)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+  use ark_std::{test_rng, UniformRand};
+  use rand_chacha::rand_core::RngCore;
+
+  use super::*;
+
+  #[test]
+  fn evaluate_matches_dense() {
+    let mut rng = test_rng();
+    let num_vars = 6;
+
+    let entries: Vec<(usize, Fr)> = (0..10)
+      .map(|_| {
+        (
+          rng.next_u64() as usize % (1 << num_vars),
+          Fr::rand(&mut rng),
+        )
+      })
+      .collect();
+    let sparse = SparsePolynomial::new(num_vars, entries);
+    let dense = sparse.to_dense();
+
+    for _ in 0..10 {
+      let r: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut rng)).collect();
+      assert_eq!(sparse.evaluate(&r), dense.evaluate(&r));
+    }
+  }
+
+  #[test]
+  fn evaluate_on_hypercube() {
+    let sparse = SparsePolynomial::new(3, vec![(0b101, Fr::from(7u64))]);
+    for index in 0..8 {
+      let r: Vec<Fr> = (0..3)
+        .map(|i| Fr::from(((index >> (2 - i)) & 1) as u64))
+        .collect();
+      let expected = if index == 0b101 { 7u64 } else { 0 };
+      assert_eq!(sparse.evaluate(&r), Fr::from(expected));
+    }
+  }
+}
@@ -4,6 +4,7 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::too_many_arguments)]
 
+use crate::utils::batch_invert;
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 use crate::utils::transcript::ProofTranscript;
@@ -12,13 +13,8 @@ use ark_ff::{Field, PrimeField};
 use ark_serialize::*;
 use ark_std::One;
 use core::iter;
-use merlin::Transcript;
 
-#[cfg(feature = "ark-msm")]
-use ark_ec::VariableBaseMSM;
-
-#[cfg(not(feature = "ark-msm"))]
-use super::super::msm::VariableBaseMSM;
+use crate::msm::backend::msm_backend;
 
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BulletReductionProof<G: CurveGroup> {
@@ -37,8 +33,8 @@ impl<G: CurveGroup> BulletReductionProof<G> {
   ///
   /// The lengths of the vectors must all be the same, and must all be
   /// either 0 or a power of 2.
-  pub fn prove(
-    transcript: &mut Transcript,
+  pub fn prove<T: ProofTranscript<G>>(
+    transcript: &mut T,
     Q: &G,
     G_vec: &[G],
     H: &G,
@@ -97,7 +93,7 @@ impl<G: CurveGroup> BulletReductionProof<G> {
 
       let bases = G::normalize_batch(bases.as_ref());
 
-      let L = VariableBaseMSM::msm(bases.as_ref(), scalars.as_ref()).unwrap();
+      let L = msm_backend::<G>(bases.len()).msm(bases.as_ref(), scalars.as_ref());
 
       let scalars = a_R
         .iter()
@@ -115,12 +111,12 @@ impl<G: CurveGroup> BulletReductionProof<G> {
 
       let bases = G::normalize_batch(bases.as_ref());
 
-      let R = VariableBaseMSM::msm(bases.as_ref(), scalars.as_ref()).unwrap();
+      let R = msm_backend::<G>(bases.len()).msm(bases.as_ref(), scalars.as_ref());
 
-      <Transcript as ProofTranscript<G>>::append_point(transcript, b"L", &L);
-      <Transcript as ProofTranscript<G>>::append_point(transcript, b"R", &R);
+      <T as ProofTranscript<G>>::append_point(transcript, b"L", &L);
+      <T as ProofTranscript<G>>::append_point(transcript, b"R", &R);
 
-      let u = <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"u");
+      let u = <T as ProofTranscript<G>>::challenge_scalar(transcript, b"u");
 
       let u_inv = u.inverse().unwrap();
 
@@ -156,10 +152,10 @@ impl<G: CurveGroup> BulletReductionProof<G> {
   /// Computes three vectors of verification scalars \\([u\_{i}^{2}]\\), \\([u\_{i}^{-2}]\\) and \\([s\_{i}]\\) for combined multiscalar multiplication
   /// in a parent protocol. See [inner product protocol notes](index.html#verification-equation) for details.
   /// The verifier must provide the input length \\(n\\) explicitly to avoid unbounded allocation within the inner product proof.
-  fn verification_scalars(
+  fn verification_scalars<T: ProofTranscript<G>>(
     &self,
     n: usize,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> Result<
     (
       Vec<G::ScalarField>,
@@ -169,6 +165,9 @@ impl<G: CurveGroup> BulletReductionProof<G> {
     ProofVerifyError,
   > {
     let lg_n = self.L_vec.len();
+    if self.R_vec.len() != lg_n {
+      return Err(ProofVerifyError::InvalidShape);
+    }
     if lg_n >= 32 {
       // 4 billion multiplications should be enough for anyone
       // and this check prevents overflow in 1<<lg_n below.
@@ -182,19 +181,16 @@ impl<G: CurveGroup> BulletReductionProof<G> {
     // 1. Recompute x_k,...,x_1 based on the proof transcript
     let mut challenges = Vec::with_capacity(lg_n);
     for (L, R) in self.L_vec.iter().zip(self.R_vec.iter()) {
-      <Transcript as ProofTranscript<G>>::append_point(transcript, b"L", L);
-      <Transcript as ProofTranscript<G>>::append_point(transcript, b"R", R);
-      challenges.push(<Transcript as ProofTranscript<G>>::challenge_scalar(
+      <T as ProofTranscript<G>>::append_point(transcript, b"L", L);
+      <T as ProofTranscript<G>>::append_point(transcript, b"R", R);
+      challenges.push(<T as ProofTranscript<G>>::challenge_scalar(
         transcript, b"u",
       ));
     }
 
     // 2. Compute 1/(u_k...u_1) and 1/u_k, ..., 1/u_1
-    // let mut challenges_inv = challenges.clone();
-    let mut challenges_inv = challenges
-      .iter()
-      .map(|x| x.inverse().unwrap())
-      .collect::<Vec<_>>();
+    let mut challenges_inv = challenges.clone();
+    batch_invert(&mut challenges_inv);
     let mut all_inv = G::ScalarField::one();
     challenges_inv.iter().for_each(|c| all_inv *= *c);
 
@@ -224,11 +220,11 @@ impl<G: CurveGroup> BulletReductionProof<G> {
   /// but for efficiency the actual protocols would use `verification_scalars`
   /// method to combine inner product verification with other checks
   /// in a single multiscalar multiplication.
-  pub fn verify(
+  pub fn verify<T: ProofTranscript<G>>(
     &self,
     n: usize,
     a: &[G::ScalarField],
-    transcript: &mut Transcript,
+    transcript: &mut T,
     Gamma: &G,
     G: &[G],
   ) -> Result<(G, G, G::ScalarField), ProofVerifyError> {
@@ -236,7 +232,7 @@ impl<G: CurveGroup> BulletReductionProof<G> {
 
     let group_element = G::normalize_batch(G);
 
-    let G_hat = VariableBaseMSM::msm(group_element.as_ref(), s.as_ref()).unwrap();
+    let G_hat = msm_backend::<G>(group_element.len()).msm(group_element.as_ref(), s.as_ref());
 
     let a_hat = inner_product(a, &s);
 
@@ -251,7 +247,7 @@ impl<G: CurveGroup> BulletReductionProof<G> {
       .chain([G::ScalarField::one()])
       .collect::<Vec<_>>();
 
-    let Gamma_hat = VariableBaseMSM::msm(bases.as_ref(), scalars.as_ref()).unwrap();
+    let Gamma_hat = msm_backend::<G>(bases.len()).msm(bases.as_ref(), scalars.as_ref());
 
     Ok((G_hat, Gamma_hat, a_hat))
   }
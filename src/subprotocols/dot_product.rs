@@ -6,7 +6,6 @@ use crate::utils::random::RandomTape;
 use crate::utils::transcript::ProofTranscript;
 use ark_ec::CurveGroup;
 use ark_serialize::*;
-use merlin::Transcript;
 
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct DotProductProof<G: CurveGroup> {
@@ -28,10 +27,10 @@ impl<G: CurveGroup> DotProductProof<G> {
   }
 
   #[allow(dead_code)]
-  pub fn prove(
+  pub fn prove<T: ProofTranscript<G>>(
     gens_1: &MultiCommitGens<G>,
     gens_n: &MultiCommitGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
     x_vec: &[G::ScalarField],
     blind_x: &G::ScalarField,
@@ -39,7 +38,7 @@ impl<G: CurveGroup> DotProductProof<G> {
     y: &G::ScalarField,
     blind_y: &G::ScalarField,
   ) -> (Self, G, G) {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProof::<G>::protocol_name(),
     );
@@ -55,22 +54,22 @@ impl<G: CurveGroup> DotProductProof<G> {
     let r_beta = random_tape.random_scalar(b"r_beta");
 
     let Cx = Commitments::batch_commit(x_vec, blind_x, gens_n);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cx", &Cx);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cx", &Cx);
 
     let Cy = y.commit(blind_y, gens_1);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cy", &Cy);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cy", &Cy);
 
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"a", a_vec);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"a", a_vec);
 
     let delta = Commitments::batch_commit(&d_vec, &r_delta, gens_n);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"delta", &delta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"delta", &delta);
 
     let dotproduct_a_d = DotProductProof::<G>::compute_dotproduct(a_vec, &d_vec);
 
     let beta = dotproduct_a_d.commit(&r_beta, gens_1);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"beta", &beta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"beta", &beta);
 
-    let c = <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
+    let c = <T as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
 
     let z = (0..d_vec.len())
       .map(|i| c * x_vec[i] + d_vec[i])
@@ -92,11 +91,11 @@ impl<G: CurveGroup> DotProductProof<G> {
     )
   }
 
-  pub fn verify(
+  pub fn verify<T: ProofTranscript<G>>(
     &self,
     gens_1: &MultiCommitGens<G>,
     gens_n: &MultiCommitGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     a: &[G::ScalarField],
     Cx: &G,
     Cy: &G,
@@ -108,19 +107,19 @@ impl<G: CurveGroup> DotProductProof<G> {
       return Err(ProofVerifyError::InvalidInputLength(1, gens_1.n));
     }
 
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProof::<G>::protocol_name(),
     );
 
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cx", Cx);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cy", Cy);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cx", Cx);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cy", Cy);
 
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"a", a);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"delta", &self.delta);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"beta", &self.beta);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"a", a);
+    <T as ProofTranscript<G>>::append_point(transcript, b"delta", &self.delta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"beta", &self.beta);
 
-    let c = <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
+    let c = <T as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
 
     let mut result =
       *Cx * c + self.delta == Commitments::batch_commit(self.z.as_ref(), &self.z_delta, gens_n);
@@ -164,9 +163,9 @@ impl<G: CurveGroup> DotProductProofLog<G> {
   }
 
   #[tracing::instrument(skip_all, name = "DotProductProofLog.prove")]
-  pub fn prove(
+  pub fn prove<T: ProofTranscript<G>>(
     gens: &DotProductProofGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
     x_vec: &[G::ScalarField],
     blind_x: &G::ScalarField,
@@ -174,7 +173,7 @@ impl<G: CurveGroup> DotProductProofLog<G> {
     y: &G::ScalarField,
     blind_y: &G::ScalarField,
   ) -> (Self, G, G) {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProofLog::<G>::protocol_name(),
     );
@@ -196,12 +195,12 @@ impl<G: CurveGroup> DotProductProofLog<G> {
     };
 
     let Cx = Commitments::batch_commit(x_vec, blind_x, &gens.gens_n);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cx", &Cx);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cx", &Cx);
 
     let Cy = y.commit(blind_y, &gens.gens_1);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cy", &Cy);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cy", &Cy);
 
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"a", a_vec);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"a", a_vec);
 
     let blind_Gamma = *blind_x + *blind_y;
     let (bullet_reduction_proof, _Gamma_hat, x_hat, a_hat, g_hat, rhat_Gamma) =
@@ -225,12 +224,12 @@ impl<G: CurveGroup> DotProductProofLog<G> {
       };
       d.commit(&r_delta, &gens_hat)
     };
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"delta", &delta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"delta", &delta);
 
     let beta = d.commit(&r_beta, &gens.gens_1);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"beta", &beta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"beta", &beta);
 
-    let c = <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
+    let c = <T as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
 
     let z1 = d + c * y_hat;
     let z2 = a_hat * (c * rhat_Gamma + r_beta) + r_delta;
@@ -248,25 +247,29 @@ impl<G: CurveGroup> DotProductProofLog<G> {
     )
   }
 
-  pub fn verify(
+  pub fn verify<T: ProofTranscript<G>>(
     &self,
     n: usize,
     gens: &DotProductProofGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     a: &[G::ScalarField],
     Cx: &G,
     Cy: &G,
   ) -> Result<(), ProofVerifyError> {
-    assert_eq!(gens.n, n);
-    assert_eq!(a.len(), n);
+    if gens.n != n {
+      return Err(ProofVerifyError::InvalidInputLength(gens.n, n));
+    }
+    if a.len() != n {
+      return Err(ProofVerifyError::InvalidInputLength(n, a.len()));
+    }
 
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProofLog::<G>::protocol_name(),
     );
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cx", Cx);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cy", Cy);
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"a", a);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cx", Cx);
+    <T as ProofTranscript<G>>::append_point(transcript, b"Cy", Cy);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"a", a);
 
     let Gamma = *Cx + *Cy;
 
@@ -275,10 +278,10 @@ impl<G: CurveGroup> DotProductProofLog<G> {
         .bullet_reduction_proof
         .verify(n, a, transcript, &Gamma, &gens.gens_n.G)?;
 
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"delta", &self.delta);
-    <Transcript as ProofTranscript<G>>::append_point(transcript, b"beta", &self.beta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"delta", &self.delta);
+    <T as ProofTranscript<G>>::append_point(transcript, b"beta", &self.beta);
 
-    let c = <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
+    let c = <T as ProofTranscript<G>>::challenge_scalar(transcript, b"c");
 
     let c_s = &c;
     let beta_s = self.beta;
@@ -302,6 +305,7 @@ mod tests {
   use ark_curve25519::EdwardsProjective as G1Projective;
   use ark_std::test_rng;
   use ark_std::UniformRand;
+  use merlin::Transcript;
 
   #[test]
   fn check_dotproductproof() {
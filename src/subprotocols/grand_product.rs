@@ -3,12 +3,12 @@
 use super::sumcheck::SumcheckInstanceProof;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
+use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 use crate::utils::transcript::ProofTranscript;
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_serialize::*;
-use merlin::Transcript;
 
 #[derive(Debug)]
 pub struct GrandProductCircuit<F> {
@@ -80,14 +80,13 @@ impl<F: PrimeField> LayerProofBatched<F> {
     num_rounds: usize,
     degree_bound: usize,
     transcript: &mut T,
-  ) -> (F, Vec<F>)
+  ) -> Result<(F, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
     self
       .proof
       .verify::<G, T>(claim, num_rounds, degree_bound, transcript)
-      .unwrap()
   }
 }
 
@@ -98,9 +97,9 @@ pub struct BatchedGrandProductArgument<F: PrimeField> {
 
 impl<F: PrimeField> BatchedGrandProductArgument<F> {
   #[tracing::instrument(skip_all, name = "BatchedGrandProductArgument.prove")]
-  pub fn prove<G>(
+  pub fn prove<G, T: ProofTranscript<G>>(
     grand_product_circuits: &mut Vec<&mut GrandProductCircuit<F>>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> (Self, Vec<F>)
   where
     G: CurveGroup<ScalarField = F>,
@@ -140,42 +139,30 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
       );
 
       // produce a fresh set of coeffs and a joint claim
-      let coeff_vec: Vec<F> = <Transcript as ProofTranscript<G>>::challenge_vector(
-        transcript,
-        b"rand_coeffs_next_layer",
-        claims_to_verify.len(),
-      );
+      let coeff_vec: Vec<F> =
+        transcript.challenge_vector(b"rand_coeffs_next_layer", claims_to_verify.len());
       let claim = (0..claims_to_verify.len())
         .map(|i| claims_to_verify[i] * coeff_vec[i])
         .sum();
 
-      let (proof, rand_prod, claims_prod) = SumcheckInstanceProof::<F>::prove_cubic_batched::<_, G>(
-        &claim,
-        num_rounds_prod,
-        poly_vec_par,
-        &coeff_vec,
-        comb_func_prod,
-        transcript,
-      );
-
-      let (claims_prod_left, claims_prod_right, _claims_eq) = claims_prod;
-      for i in 0..grand_product_circuits.len() {
-        <Transcript as ProofTranscript<G>>::append_scalar(
+      let (proof, rand_prod, claims_prod) =
+        SumcheckInstanceProof::<F>::prove_cubic_batched::<_, G, T>(
+          &claim,
+          num_rounds_prod,
+          poly_vec_par,
+          &coeff_vec,
+          comb_func_prod,
           transcript,
-          b"claim_prod_left",
-          &claims_prod_left[i],
         );
 
-        <Transcript as ProofTranscript<G>>::append_scalar(
-          transcript,
-          b"claim_prod_right",
-          &claims_prod_right[i],
-        );
+      let (claims_prod_left, claims_prod_right, _claims_eq) = claims_prod;
+      for i in 0..grand_product_circuits.len() {
+        transcript.append_scalar(b"claim_prod_left", &claims_prod_left[i]);
+        transcript.append_scalar(b"claim_prod_right", &claims_prod_right[i]);
       }
 
       // produce a random challenge to condense two claims into a single claim
-      let r_layer =
-        <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"challenge_r_layer");
+      let r_layer = transcript.challenge_scalar(b"challenge_r_layer");
 
       claims_to_verify = (0..grand_product_circuits.len())
         .map(|i| claims_prod_left[i] + r_layer * (claims_prod_right[i] - claims_prod_left[i]))
@@ -200,64 +187,159 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
     )
   }
 
+  /// Number of layers in the proof, i.e. log2 of the number of leaves in each circuit.
+  pub fn num_layers(&self) -> usize {
+    self.proof.len()
+  }
+
+  /// Number of grand product circuits batched into the proof.
+  pub fn num_circuits(&self) -> usize {
+    self
+      .proof
+      .first()
+      .map_or(0, |layer| layer.claims_prod_left.len())
+  }
+
+  /// Compressed serialized size of a proof batching `num_circuits` circuits of `len` leaves each,
+  /// computed without running the prover. Layer `i` holds an `i`-round sumcheck whose cubic
+  /// round polynomials store 3 coefficients each, plus a left and right claim per circuit.
+  pub fn proof_size_bytes(num_circuits: usize, len: usize) -> usize {
+    let vec_len_prefix = 0u64.compressed_size();
+    let field_element = F::zero().compressed_size();
+    let layers: usize = (0..len.log_2())
+      .map(|num_rounds| {
+        let sumcheck = vec_len_prefix + num_rounds * (vec_len_prefix + 3 * field_element);
+        let claims = 2 * (vec_len_prefix + num_circuits * field_element);
+        sumcheck + claims
+      })
+      .sum();
+    vec_len_prefix + layers
+  }
+
+  /// Cheap structural check that the proof has one layer per level of a `len`-leaf circuit and
+  /// `num_claims` left/right claims per layer. Lets the verifier reject malformed proofs
+  /// before doing any sumcheck work.
+  pub fn check_shape(&self, num_claims: usize, len: usize) -> Result<(), ProofVerifyError> {
+    if self.proof.len() != len.log_2() {
+      return Err(ProofVerifyError::InvalidShape);
+    }
+    for layer in self.proof.iter() {
+      if layer.claims_prod_left.len() != num_claims || layer.claims_prod_right.len() != num_claims {
+        return Err(ProofVerifyError::InvalidShape);
+      }
+    }
+    Ok(())
+  }
+
   pub fn verify<G, T: ProofTranscript<G>>(
     &self,
     claims_prod_vec: &Vec<F>,
     len: usize,
     transcript: &mut T,
-  ) -> (Vec<F>, Vec<F>)
+  ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
-    let num_layers = len.log_2() as usize;
-    let mut rand: Vec<F> = Vec::new();
-    assert_eq!(self.proof.len(), num_layers);
-
-    let mut claims_to_verify = claims_prod_vec.to_owned();
-    for (num_rounds, i) in (0..num_layers).enumerate() {
-      // produce random coefficients, one for each instance
-      let coeff_vec =
-        transcript.challenge_vector(b"rand_coeffs_next_layer", claims_to_verify.len());
+    self.check_shape(claims_prod_vec.len(), len)?;
 
-      // produce a joint claim
-      let claim = (0..claims_to_verify.len())
-        .map(|i| claims_to_verify[i] * coeff_vec[i])
-        .sum();
+    let mut verifier = GrandProductVerifier::new(claims_prod_vec, len);
+    for layer in self.proof.iter() {
+      verifier.verify_layer::<G, T>(layer, transcript)?;
+    }
+    verifier.finish()
+  }
+}
 
-      let (claim_last, rand_prod) = self.proof[i].verify::<G, T>(claim, num_rounds, 3, transcript);
+/// Verifies a `BatchedGrandProductArgument` one layer at a time, from the root down, so that
+/// layer proofs can be read, checked and dropped without holding the whole proof. Produces the
+/// same transcript interaction as `BatchedGrandProductArgument::verify`.
+pub struct GrandProductVerifier<F: PrimeField> {
+  claims_to_verify: Vec<F>,
+  rand: Vec<F>,
+  num_layers: usize,
+}
 
-      let claims_prod_left = &self.proof[i].claims_prod_left;
-      let claims_prod_right = &self.proof[i].claims_prod_right;
-      assert_eq!(claims_prod_left.len(), claims_prod_vec.len());
-      assert_eq!(claims_prod_right.len(), claims_prod_vec.len());
+impl<F: PrimeField> GrandProductVerifier<F> {
+  /// Starts verifying the grand products `claims_prod_vec` of circuits with `len` leaves each.
+  pub fn new(claims_prod_vec: &[F], len: usize) -> Self {
+    GrandProductVerifier {
+      claims_to_verify: claims_prod_vec.to_vec(),
+      rand: Vec::new(),
+      num_layers: len.log_2(),
+    }
+  }
 
-      for i in 0..claims_prod_vec.len() {
-        transcript.append_scalar(b"claim_prod_left", &claims_prod_left[i]);
-        transcript.append_scalar(b"claim_prod_right", &claims_prod_right[i]);
-      }
+  /// Verifies the next layer, reducing the current claims to claims about the layer below.
+  pub fn verify_layer<G, T: ProofTranscript<G>>(
+    &mut self,
+    layer: &LayerProofBatched<F>,
+    transcript: &mut T,
+  ) -> Result<(), ProofVerifyError>
+  where
+    G: CurveGroup<ScalarField = F>,
+  {
+    let num_claims = self.claims_to_verify.len();
+    let num_rounds = self.rand.len();
+    if num_rounds >= self.num_layers
+      || layer.claims_prod_left.len() != num_claims
+      || layer.claims_prod_right.len() != num_claims
+    {
+      return Err(ProofVerifyError::InvalidShape);
+    }
 
-      assert_eq!(rand.len(), rand_prod.len());
-      let eq: F = (0..rand.len())
-        .map(|i| rand[i] * rand_prod[i] + (F::one() - rand[i]) * (F::one() - rand_prod[i]))
-        .product();
-      let claim_expected: F = (0..claims_prod_vec.len())
-        .map(|i| coeff_vec[i] * (claims_prod_left[i] * claims_prod_right[i] * eq))
-        .sum();
+    // produce random coefficients, one for each instance
+    let coeff_vec = transcript.challenge_vector(b"rand_coeffs_next_layer", num_claims);
 
-      assert_eq!(claim_expected, claim_last);
+    // produce a joint claim
+    let claim = (0..num_claims)
+      .map(|i| self.claims_to_verify[i] * coeff_vec[i])
+      .sum();
 
-      // produce a random challenge
-      let r_layer = transcript.challenge_scalar(b"challenge_r_layer");
+    let (claim_last, rand_prod) = layer.verify::<G, T>(claim, num_rounds, 3, transcript)?;
 
-      claims_to_verify = (0..claims_prod_left.len())
-        .map(|i| claims_prod_left[i] + r_layer * (claims_prod_right[i] - claims_prod_left[i]))
-        .collect::<Vec<F>>();
+    let claims_prod_left = &layer.claims_prod_left;
+    let claims_prod_right = &layer.claims_prod_right;
 
-      let mut ext = vec![r_layer];
-      ext.extend(rand_prod);
-      rand = ext;
+    for i in 0..num_claims {
+      transcript.append_scalar(b"claim_prod_left", &claims_prod_left[i]);
+      transcript.append_scalar(b"claim_prod_right", &claims_prod_right[i]);
+    }
+
+    let rand = &self.rand;
+    if rand.len() != rand_prod.len() {
+      return Err(ProofVerifyError::InvalidShape);
+    }
+    let eq: F = (0..rand.len())
+      .map(|i| rand[i] * rand_prod[i] + (F::one() - rand[i]) * (F::one() - rand_prod[i]))
+      .product();
+    let claim_expected: F = (0..num_claims)
+      .map(|i| coeff_vec[i] * (claims_prod_left[i] * claims_prod_right[i] * eq))
+      .sum();
+
+    if claim_expected != claim_last {
+      return Err(ProofVerifyError::ClaimMismatch);
     }
-    (claims_to_verify, rand)
+
+    // produce a random challenge
+    let r_layer = transcript.challenge_scalar(b"challenge_r_layer");
+
+    self.claims_to_verify = (0..num_claims)
+      .map(|i| claims_prod_left[i] + r_layer * (claims_prod_right[i] - claims_prod_left[i]))
+      .collect::<Vec<F>>();
+
+    let mut ext = vec![r_layer];
+    ext.extend(rand_prod);
+    self.rand = ext;
+    Ok(())
+  }
+
+  /// Returns the claimed leaf evaluations and the point they are claimed at, once every layer has
+  /// been verified.
+  pub fn finish(self) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
+    if self.rand.len() != self.num_layers {
+      return Err(ProofVerifyError::InvalidShape);
+    }
+    Ok((self.claims_to_verify, self.rand))
   }
 }
 
@@ -265,6 +347,7 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
 mod grand_product_circuit_tests {
   use super::*;
   use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+  use merlin::Transcript;
 
   #[test]
   fn prove_verify() {
@@ -276,9 +359,143 @@ mod grand_product_circuit_tests {
     let mut transcript = Transcript::new(b"test_transcript");
     let mut circuits_vec = vec![&mut factorial_circuit];
     let (proof, _) =
-      BatchedGrandProductArgument::prove::<G1Projective>(&mut circuits_vec, &mut transcript);
+      BatchedGrandProductArgument::prove::<G1Projective, _>(&mut circuits_vec, &mut transcript);
+
+    let mut transcript = Transcript::new(b"test_transcript");
+    assert!(proof
+      .verify::<G1Projective, _>(&expected_eval, 4, &mut transcript)
+      .is_ok());
+  }
+
+  #[test]
+  fn proof_size() {
+    let leaves = DensePolynomial::new((1..=8).map(Fr::from).collect());
+    let mut circuit_a = GrandProductCircuit::new(&leaves);
+    let mut circuit_b = GrandProductCircuit::new(&leaves);
+
+    let mut transcript = Transcript::new(b"test_transcript");
+    let mut circuits_vec = vec![&mut circuit_a, &mut circuit_b];
+    let (proof, _) =
+      BatchedGrandProductArgument::prove::<G1Projective, _>(&mut circuits_vec, &mut transcript);
+
+    assert_eq!(proof.num_layers(), 3);
+    assert_eq!(proof.num_circuits(), 2);
+    assert_eq!(
+      BatchedGrandProductArgument::<Fr>::proof_size_bytes(2, 8),
+      proof.compressed_size()
+    );
+  }
+
+  #[cfg(feature = "blake3")]
+  #[test]
+  fn prove_verify_blake3() {
+    use crate::utils::transcript::Blake3Transcript;
+
+    let factorial = DensePolynomial::new(vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)]);
+    let mut factorial_circuit = GrandProductCircuit::new(&factorial);
+    let expected_eval = vec![Fr::from(24)];
+
+    let mut transcript = Blake3Transcript::new(b"test_transcript");
+    let mut circuits_vec = vec![&mut factorial_circuit];
+    let (proof, prover_rand) =
+      BatchedGrandProductArgument::prove::<G1Projective, _>(&mut circuits_vec, &mut transcript);
+
+    let mut transcript = Blake3Transcript::new(b"test_transcript");
+    let (claims, verifier_rand) = proof
+      .verify::<G1Projective, _>(&expected_eval, 4, &mut transcript)
+      .unwrap();
+    assert_eq!(prover_rand, verifier_rand);
+    assert_eq!(claims.len(), 1);
+  }
+
+  #[test]
+  fn wrong_claim_rejected() {
+    let factorial = DensePolynomial::new(vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)]);
+    let mut factorial_circuit = GrandProductCircuit::new(&factorial);
+
+    let mut transcript = Transcript::new(b"test_transcript");
+    let mut circuits_vec = vec![&mut factorial_circuit];
+    let (proof, _) =
+      BatchedGrandProductArgument::prove::<G1Projective, _>(&mut circuits_vec, &mut transcript);
 
+    // 4! = 24
+    let wrong_eval = vec![Fr::from(25)];
     let mut transcript = Transcript::new(b"test_transcript");
-    proof.verify::<G1Projective, _>(&expected_eval, 4, &mut transcript);
+    assert!(matches!(
+      proof.verify::<G1Projective, _>(&wrong_eval, 4, &mut transcript),
+      Err(ProofVerifyError::ClaimMismatch)
+    ));
+  }
+
+  #[test]
+  fn check_shape() {
+    let leaves = DensePolynomial::new(vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)]);
+    let mut circuit_a = GrandProductCircuit::new(&leaves);
+    let mut circuit_b = GrandProductCircuit::new(&leaves);
+
+    let mut transcript = Transcript::new(b"test_transcript");
+    let mut circuits_vec = vec![&mut circuit_a, &mut circuit_b];
+    let (mut proof, _) =
+      BatchedGrandProductArgument::prove::<G1Projective, _>(&mut circuits_vec, &mut transcript);
+    assert!(proof.check_shape(2, 4).is_ok());
+    assert!(matches!(
+      proof.check_shape(2, 8),
+      Err(ProofVerifyError::InvalidShape)
+    ));
+    assert!(matches!(
+      proof.check_shape(3, 4),
+      Err(ProofVerifyError::InvalidShape)
+    ));
+
+    proof.proof[0].claims_prod_right.pop();
+    assert!(matches!(
+      proof.check_shape(2, 4),
+      Err(ProofVerifyError::InvalidShape)
+    ));
+  }
+
+  #[test]
+  fn streaming_matches_eager() {
+    let factorial = DensePolynomial::new((1..=8).map(Fr::from).collect());
+    let mut factorial_circuit = GrandProductCircuit::new(&factorial);
+    let claims = vec![Fr::from(40320)];
+
+    let mut transcript = Transcript::new(b"test_transcript");
+    let mut circuits_vec = vec![&mut factorial_circuit];
+    let (mut proof, _) =
+      BatchedGrandProductArgument::prove::<G1Projective, _>(&mut circuits_vec, &mut transcript);
+
+    let streaming = |proof: &BatchedGrandProductArgument<Fr>, claims: &Vec<Fr>| {
+      let mut transcript = Transcript::new(b"test_transcript");
+      let mut verifier = GrandProductVerifier::new(claims, 8);
+      for layer in proof.proof.iter() {
+        verifier.verify_layer::<G1Projective, _>(layer, &mut transcript)?;
+      }
+      let result = verifier.finish();
+      let challenge: Fr =
+        <Transcript as ProofTranscript<G1Projective>>::challenge_scalar(&mut transcript, b"test");
+      result.map(|result| (result, challenge))
+    };
+    let eager = |proof: &BatchedGrandProductArgument<Fr>, claims: &Vec<Fr>| {
+      let mut transcript = Transcript::new(b"test_transcript");
+      let result = proof.verify::<G1Projective, _>(claims, 8, &mut transcript);
+      let challenge: Fr =
+        <Transcript as ProofTranscript<G1Projective>>::challenge_scalar(&mut transcript, b"test");
+      result.map(|result| (result, challenge))
+    };
+
+    // Same result and same transcript state afterwards
+    assert_eq!(
+      streaming(&proof, &claims).unwrap(),
+      eager(&proof, &claims).unwrap()
+    );
+
+    let wrong_claims = vec![Fr::from(40321)];
+    assert!(streaming(&proof, &wrong_claims).is_err());
+    assert!(eager(&proof, &wrong_claims).is_err());
+
+    proof.proof.pop();
+    assert!(streaming(&proof, &claims).is_err());
+    assert!(eager(&proof, &claims).is_err());
   }
 }
@@ -13,18 +13,28 @@ use ark_serialize::*;
 use ark_std::One;
 use merlin::Transcript;
 
-#[cfg(feature = "ark-msm")]
-use ark_ec::VariableBaseMSM;
-
-#[cfg(not(feature = "ark-msm"))]
-use crate::msm::VariableBaseMSM;
+use crate::msm::backend::msm_backend;
 
 #[cfg(feature = "multicore")]
 use rayon::prelude::*;
 
 impl<F: PrimeField> SumcheckInstanceProof<F> {
+  /// Create a single sumcheck proof for a random linear combination of cubic sumcheck instances
+  /// `sum_x comb_func(A_i(x), B_i(x), C(x))`, where the instances share the polynomial `C`.
+  ///
+  /// Params
+  /// - `claim`: Batched claim, `sum_i coeffs[i] * claim_i`
+  /// - `num_rounds`: Number of rounds of sumcheck, or number of variables to bind
+  /// - `poly_vec_par`: (A_i polys, B_i polys, shared C poly)
+  /// - `coeffs`: Batching coefficients, one per instance
+  /// - `comb_func`: Degree-3 function combining A_i, B_i and C evaluations
+  /// - `transcript`: Fiat-shamir transcript
+  ///
+  /// Returns (SumcheckInstanceProof, r_eval_point, (A_i(r), B_i(r), C(r)))
+  /// - The verifier recovers the per-instance claims at `r` from the final evaluations and checks
+  ///   `sum_i coeffs[i] * comb_func(A_i(r), B_i(r), C(r))` against the verified evaluation.
   #[tracing::instrument(skip_all, name = "Sumcheck.prove_batched")]
-  pub fn prove_cubic_batched<Func, G>(
+  pub fn prove_cubic_batched<Func, G, T: ProofTranscript<G>>(
     claim: &F,
     num_rounds: usize,
     poly_vec_par: (
@@ -34,7 +44,7 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     ),
     coeffs: &[F],
     comb_func: Func,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> (Self, Vec<F>, (Vec<F>, Vec<F>, F))
   where
     Func: Fn(&F, &F, &F) -> F + Sync,
@@ -108,8 +118,7 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
       <UniPoly<F> as AppendToTranscript<G>>::append_to_transcript(&poly, b"poly", transcript);
 
       //derive the verifier's challenge for the next round
-      let r_j =
-        <Transcript as ProofTranscript<G>>::challenge_scalar(transcript, b"challenge_nextround");
+      let r_j = transcript.challenge_scalar(b"challenge_nextround");
       r.push(r_j);
 
       // bound all tables to the verifier's challenege
@@ -285,20 +294,21 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     let mut r: Vec<F> = Vec::new();
 
     // verify that there is a univariate polynomial for each round
-    assert_eq!(self.compressed_polys.len(), num_rounds);
+    if self.compressed_polys.len() != num_rounds {
+      return Err(ProofVerifyError::InvalidShape);
+    }
     for i in 0..self.compressed_polys.len() {
-      let poly = self.compressed_polys[i].decompress(&e);
-
-      // verify degree bound
-      if poly.degree() != degree_bound {
-        return Err(ProofVerifyError::InvalidInputLength(
-          degree_bound,
-          poly.degree(),
-        ));
+      // verify degree bound before decompressing, which needs a constant term
+      let degree = self.compressed_polys[i].degree();
+      if degree != degree_bound {
+        return Err(ProofVerifyError::InvalidShape);
       }
+      let poly = self.compressed_polys[i].decompress(&e);
 
       // check if G_k(0) + G_k(1) = e
-      assert_eq!(poly.eval_at_zero() + poly.eval_at_one(), e);
+      if poly.eval_at_zero() + poly.eval_at_one() != e {
+        return Err(ProofVerifyError::ClaimMismatch);
+      }
 
       // append the prover's message to the transcript
       <UniPoly<F> as AppendToTranscript<G>>::append_to_transcript(&poly, b"poly", transcript);
@@ -387,7 +397,7 @@ impl<G: CurveGroup> ZKSumcheckInstanceProof<G> {
         // compute a weighted sum of the RHS
         let bases = vec![comm_claim_per_round.into_affine(), comm_eval.into_affine()];
 
-        let comm_target = VariableBaseMSM::msm(bases.as_ref(), w.as_ref()).unwrap();
+        let comm_target = msm_backend::<G>(bases.len()).msm(bases.as_ref(), w.as_ref());
 
         let a = {
           // the vector to use to decommit for sum-check test
@@ -425,7 +435,7 @@ impl<G: CurveGroup> ZKSumcheckInstanceProof<G> {
           .is_ok()
       };
       if !res {
-        return Err(ProofVerifyError::InternalError);
+        return Err(ProofVerifyError::ClaimMismatch);
       }
 
       r.push(r_i);
@@ -443,6 +453,69 @@ mod test {
   use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
   use ark_ff::Zero;
 
+  #[test]
+  fn sumcheck_cubic_batched() {
+    use ark_std::{test_rng, UniformRand};
+
+    let mut rng = test_rng();
+    let num_vars = 4;
+    let num_evals = num_vars.pow2();
+    let mut random_poly = || {
+      DensePolynomial::new(
+        (0..num_evals)
+          .map(|_| Fr::rand(&mut rng))
+          .collect::<Vec<Fr>>(),
+      )
+    };
+
+    let As = vec![random_poly(), random_poly()];
+    let Bs = vec![random_poly(), random_poly()];
+    let C = random_poly();
+    let comb_func = |a: &Fr, b: &Fr, c: &Fr| -> Fr { *a * *b * *c };
+
+    // independent claims for each instance
+    let claims: Vec<Fr> = As
+      .iter()
+      .zip(Bs.iter())
+      .map(|(A, B)| (0..num_evals).map(|i| comb_func(&A[i], &B[i], &C[i])).sum())
+      .collect();
+    let coeffs = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let batched_claim: Fr = claims.iter().zip(coeffs.iter()).map(|(c, k)| *c * k).sum();
+
+    let (mut As_par, mut Bs_par, mut C_par) = (As.clone(), Bs.clone(), C.clone());
+    let mut prover_transcript = Transcript::new(b"example");
+    let (proof, r_prover, (A_evals, B_evals, C_eval)) =
+      SumcheckInstanceProof::<Fr>::prove_cubic_batched::<_, G1Projective, _>(
+        &batched_claim,
+        num_vars,
+        (
+          &mut As_par.iter_mut().collect(),
+          &mut Bs_par.iter_mut().collect(),
+          &mut C_par,
+        ),
+        &coeffs,
+        comb_func,
+        &mut prover_transcript,
+      );
+
+    let mut verifier_transcript = Transcript::new(b"example");
+    let (e, r_verifier) = proof
+      .verify::<G1Projective, _>(batched_claim, num_vars, 3, &mut verifier_transcript)
+      .unwrap();
+    assert_eq!(r_prover, r_verifier);
+
+    // the final evaluations are the per-instance openings at r
+    assert_eq!(C_eval, C.evaluate(&r_verifier));
+    for i in 0..2 {
+      assert_eq!(A_evals[i], As[i].evaluate(&r_verifier));
+      assert_eq!(B_evals[i], Bs[i].evaluate(&r_verifier));
+    }
+    let expected: Fr = (0..2)
+      .map(|i| coeffs[i] * comb_func(&A_evals[i], &B_evals[i], &C_eval))
+      .sum();
+    assert_eq!(e, expected);
+  }
+
   #[test]
   fn sumcheck_arbitrary_cubic() {
     // Create three dense polynomials (all the same)
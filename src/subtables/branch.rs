@@ -0,0 +1,144 @@
+use ark_ff::PrimeField;
+
+use crate::utils::instruction_utils::combine_less_than;
+
+use super::{
+  eq::{evaluate_eq_mle, materialize_eq, EqSubtableStrategy},
+  slt::SLTSubtableStrategy,
+  SubtableStrategy,
+};
+
+/// Branch taken iff x == y, which is exactly the EQ lookup.
+pub type BEQSubtableStrategy = EqSubtableStrategy;
+
+/// Branch taken iff x != y. Shares the EQ subtable with BEQ.
+pub enum BNESubtableStrategy {}
+
+/// Branch taken iff x < y (signed), which is exactly the SLT lookup.
+pub type BLTSubtableStrategy = SLTSubtableStrategy;
+
+/// Branch taken iff x >= y (signed). Shares the SLT subtables and negates the result.
+pub enum BGESubtableStrategy {}
+
+impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
+  for BNESubtableStrategy
+{
+  const NUM_SUBTABLES: usize = 1;
+  const NUM_MEMORIES: usize = C;
+
+  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
+    [materialize_eq(M)]
+  }
+
+  fn evaluate_subtable_mle(_: usize, point: &[F]) -> F {
+    evaluate_eq_mle(point)
+  }
+
+  /// T = 1 - EQ[0] * EQ[1] * ... * EQ[C-1]
+  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
+    F::one() - vals.iter().product::<F>()
+  }
+
+  fn g_poly_degree() -> usize {
+    C
+  }
+}
+
+impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
+  for BGESubtableStrategy
+{
+  const NUM_SUBTABLES: usize = 3;
+  const NUM_MEMORIES: usize = 2 * C;
+
+  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
+    <SLTSubtableStrategy as SubtableStrategy<F, C, M>>::materialize_subtables()
+  }
+
+  fn evaluate_subtable_mle(subtable_index: usize, point: &[F]) -> F {
+    <SLTSubtableStrategy as SubtableStrategy<F, C, M>>::evaluate_subtable_mle(subtable_index, point)
+  }
+
+  fn memory_to_subtable_index(memory_index: usize) -> usize {
+    <SLTSubtableStrategy as SubtableStrategy<F, C, M>>::memory_to_subtable_index(memory_index)
+  }
+
+  fn memory_to_dimension_index(memory_index: usize) -> usize {
+    <SLTSubtableStrategy as SubtableStrategy<F, C, M>>::memory_to_dimension_index(memory_index)
+  }
+
+  /// Assumes `vals` are ordered as in SLT: SLT[0], EQ[0], LT[1], EQ[1], ... LT[C-1], EQ[C-1]
+  /// T = 1 - (SLT[0] + LT[1]*EQ[0] + ... + LT[C-1]*EQ[0]*...*EQ[C-2])
+  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
+    F::one() - combine_less_than(vals)
+  }
+
+  fn g_poly_degree() -> usize {
+    C
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+
+  use crate::{
+    g_poly_degree_test, materialization_mle_parity_test, utils::index_to_field_bitvector,
+  };
+
+  use super::*;
+
+  /// Chunk index for 4-bit operands split into two 2-bit chunks.
+  fn chunk(x: usize, y: usize, shift: usize) -> usize {
+    (((x >> shift) & 0b11) << 2) | ((y >> shift) & 0b11)
+  }
+
+  fn sign_extend(v: usize) -> i8 {
+    ((v as i8) << 4) >> 4
+  }
+
+  #[test]
+  fn branch_flags() {
+    const C: usize = 2;
+    const M: usize = 16;
+    let [eq]: [Vec<Fr>; 1] =
+      <BEQSubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    let [lt, _, slt]: [Vec<Fr>; 3] =
+      <BGESubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+
+    // Includes the x == y case for every operand
+    for x in 0..16usize {
+      for y in 0..16usize {
+        let (hi, lo) = (chunk(x, y, 2), chunk(x, y, 0));
+        let eq_vals: [Fr; C] = [eq[hi], eq[lo]];
+        let cmp_vals: [Fr; C * 2] = [slt[hi], eq[hi], lt[lo], eq[lo]];
+        let flag = |taken: bool| Fr::from(u64::from(taken));
+
+        assert_eq!(
+          <BEQSubtableStrategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&eq_vals),
+          flag(x == y),
+          "BEQ {x}, {y}"
+        );
+        assert_eq!(
+          <BNESubtableStrategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&eq_vals),
+          flag(x != y),
+          "BNE {x}, {y}"
+        );
+        assert_eq!(
+          <BLTSubtableStrategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&cmp_vals),
+          flag(sign_extend(x) < sign_extend(y)),
+          "BLT {x}, {y}"
+        );
+        assert_eq!(
+          <BGESubtableStrategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&cmp_vals),
+          flag(sign_extend(x) >= sign_extend(y)),
+          "BGE {x}, {y}"
+        );
+      }
+    }
+  }
+
+  materialization_mle_parity_test!(bne_materialization_parity, BNESubtableStrategy, Fr, 16, 1);
+  materialization_mle_parity_test!(bge_materialization_parity, BGESubtableStrategy, Fr, 16, 3);
+  g_poly_degree_test!(bne_g_poly_degree, BNESubtableStrategy, Fr, 4, 16);
+  g_poly_degree_test!(bge_g_poly_degree, BGESubtableStrategy, Fr, 4, 16);
+}
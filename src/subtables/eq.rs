@@ -0,0 +1,103 @@
+use ark_ff::PrimeField;
+use ark_std::log2;
+
+use crate::utils::split_bits;
+
+use super::SubtableStrategy;
+
+/// Equality of two operands. Every chunk reads a single EQ subtable which is 1 on the diagonal
+/// of the chunk-pair index space, and the per-chunk signals are multiplied together.
+pub enum EqSubtableStrategy {}
+
+/// Materializes the EQ subtable in counting order where lhs | rhs counts 0->m.
+pub(crate) fn materialize_eq<F: PrimeField>(m: usize) -> Vec<F> {
+  let bits_per_operand = (log2(m) / 2) as usize;
+
+  (0..m)
+    .map(|idx| {
+      let (lhs, rhs) = split_bits(idx, bits_per_operand);
+      F::from(u64::from(lhs == rhs))
+    })
+    .collect()
+}
+
+/// EQ = \prod_i (x_i * y_i + (1 - x_i) * (1 - y_i))
+pub(crate) fn evaluate_eq_mle<F: PrimeField>(point: &[F]) -> F {
+  debug_assert!(point.len() % 2 == 0);
+  let b = point.len() / 2;
+  let (x, y) = point.split_at(b);
+
+  let mut eq_term = F::one();
+  for i in 0..b {
+    eq_term *= F::one() - x[i] - y[i] + F::from(2u64) * x[i] * y[i];
+  }
+  eq_term
+}
+
+impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
+  for EqSubtableStrategy
+{
+  const NUM_SUBTABLES: usize = 1;
+  const NUM_MEMORIES: usize = C;
+
+  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
+    [materialize_eq(M)]
+  }
+
+  fn evaluate_subtable_mle(_: usize, point: &[F]) -> F {
+    evaluate_eq_mle(point)
+  }
+
+  /// T = EQ[0] * EQ[1] * ... * EQ[C-1]
+  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
+    vals.iter().product()
+  }
+
+  fn g_poly_degree() -> usize {
+    C
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+
+  use crate::{
+    g_poly_degree_test, materialization_mle_parity_test, utils::index_to_field_bitvector,
+  };
+
+  use super::*;
+
+  #[test]
+  fn table_materialization_hardcoded() {
+    const C: usize = 4;
+    const M: usize = 1 << 4;
+
+    let [eq]: [Vec<Fr>; 1] =
+      <EqSubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    for (idx, entry) in eq.iter().enumerate() {
+      let (lhs, rhs) = split_bits(idx, 2);
+      assert_eq!(*entry, Fr::from(u64::from(lhs == rhs)), "index {idx}");
+    }
+    assert_eq!(eq.iter().sum::<Fr>(), Fr::from(4u64));
+  }
+
+  #[test]
+  fn combine() {
+    let one = Fr::from(1u64);
+    let zero = Fr::from(0u64);
+    let combine = <EqSubtableStrategy as SubtableStrategy<Fr, 4, 16>>::combine_lookups;
+    assert_eq!(combine(&[one, one, one, one]), one);
+    assert_eq!(combine(&[one, one, zero, one]), zero);
+  }
+
+  materialization_mle_parity_test!(materialization_parity, EqSubtableStrategy, Fr, 16, 1);
+  materialization_mle_parity_test!(
+    materialization_parity_big_m,
+    EqSubtableStrategy,
+    Fr,
+    1 << 8,
+    1
+  );
+  g_poly_degree_test!(g_poly_degree, EqSubtableStrategy, Fr, 4, 16);
+}
@@ -1,9 +1,9 @@
 use ark_ff::PrimeField;
 use ark_std::log2;
 
-use crate::utils::split_bits;
+use crate::utils::{instruction_utils::combine_less_than, split_bits};
 
-use super::SubtableStrategy;
+use super::{eq::evaluate_eq_mle, SubtableStrategy};
 
 pub enum LTSubtableStrategy {}
 
@@ -46,11 +46,7 @@ impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
       result
     } else {
       // EQ subtable
-      let mut eq_term = F::one();
-      for i in 0..b {
-        eq_term *= F::one() - x[i] - y[i] + F::from(2u64) * x[i] * y[i];
-      }
-      eq_term
+      evaluate_eq_mle(point)
     }
   }
 
@@ -58,14 +54,7 @@ impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
   /// Assumes `vals` are ordered: LT[0], EQ[0], ... LT[C], EQ[C]
   /// T = LT[0] + LT[1]*EQ[0] + ... + LT[C]*EQ[0]*...*EQ[C-1]
   fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
-    let mut sum = F::zero();
-    let mut eq_prod = F::one();
-
-    for i in 0..C {
-      sum += vals[2 * i] * eq_prod;
-      eq_prod *= vals[2 * i + 1];
-    }
-    sum
+    combine_less_than(vals)
   }
 
   fn g_poly_degree() -> usize {
@@ -78,7 +67,9 @@ mod test {
   use ark_curve25519::Fr;
   use ark_std::{One, Zero};
 
-  use crate::{materialization_mle_parity_test, utils::index_to_field_bitvector};
+  use crate::{
+    g_poly_degree_test, materialization_mle_parity_test, utils::index_to_field_bitvector,
+  };
 
   use super::*;
 
@@ -138,6 +129,25 @@ mod test {
                                        // ...
   }
 
+  #[test]
+  fn unsigned_comparison() {
+    const C: usize = 2;
+    const M: usize = 16;
+    let [lt, eq]: [Vec<Fr>; 2] =
+      <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+
+    // 4-bit operands split into two 2-bit chunks, most significant chunk first
+    for x in 0..16usize {
+      for y in 0..16usize {
+        let chunk = |shift: usize| (((x >> shift) & 0b11) << 2) | ((y >> shift) & 0b11);
+        let (hi, lo) = (chunk(2), chunk(0));
+        let vals: [Fr; C * 2] = [lt[hi], eq[hi], lt[lo], eq[lo]];
+        let combined = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&vals);
+        assert_eq!(combined, Fr::from(u64::from(x < y)), "{x} < {y}");
+      }
+    }
+  }
+
   materialization_mle_parity_test!(
     lt_materialization_parity_test,
     LTSubtableStrategy,
@@ -145,4 +155,12 @@ mod test {
     /* m = */ 16,
     /* NUM_SUBTABLES = */ 2
   );
+  materialization_mle_parity_test!(
+    lt_materialization_parity_test_big_m,
+    LTSubtableStrategy,
+    Fr,
+    /* m = */ 1 << 8,
+    /* NUM_SUBTABLES = */ 2
+  );
+  g_poly_degree_test!(g_poly_degree, LTSubtableStrategy, Fr, 4, 16);
 }
@@ -1,10 +1,10 @@
 use std::marker::{PhantomData, Sync};
+use std::sync::Arc;
 
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::Zero;
-use merlin::Transcript;
 
 use crate::{
   lasso::{densified::DensifiedRepresentation, memory_checking::GrandProducts},
@@ -20,9 +20,13 @@ use crate::{
 use rayon::prelude::*;
 
 pub mod and;
+pub mod branch;
+pub mod eq;
 pub mod lt;
 pub mod or;
 pub mod range_check;
+pub mod sign_extend;
+pub mod slt;
 pub mod xor;
 
 #[cfg(test)]
@@ -92,13 +96,48 @@ pub trait SubtableStrategy<F: PrimeField, const C: usize, const M: usize> {
   }
 }
 
+/// Materialized subtables T_1, ..., T_{\alpha} for a strategy and memory size `M`. These don't
+/// depend on the lookups, so they can be materialized once and shared across proofs.
+pub struct SubtablePreprocessing<F: PrimeField, const C: usize, const M: usize, S>
+where
+  S: SubtableStrategy<F, C, M>,
+  [(); S::NUM_SUBTABLES]: Sized,
+{
+  subtable_entries: Arc<[Vec<F>; S::NUM_SUBTABLES]>,
+  strategy: PhantomData<S>,
+}
+
+impl<F: PrimeField, const C: usize, const M: usize, S> SubtablePreprocessing<F, C, M, S>
+where
+  S: SubtableStrategy<F, C, M>,
+  [(); S::NUM_SUBTABLES]: Sized,
+{
+  #[tracing::instrument(skip_all, name = "SubtablePreprocessing.new")]
+  pub fn new() -> Self {
+    SubtablePreprocessing {
+      subtable_entries: Arc::new(S::materialize_subtables()),
+      strategy: PhantomData,
+    }
+  }
+}
+
+impl<F: PrimeField, const C: usize, const M: usize, S> Default for SubtablePreprocessing<F, C, M, S>
+where
+  S: SubtableStrategy<F, C, M>,
+  [(); S::NUM_SUBTABLES]: Sized,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 pub struct Subtables<F: PrimeField, const C: usize, const M: usize, S>
 where
   S: SubtableStrategy<F, C, M>,
   [(); S::NUM_SUBTABLES]: Sized,
   [(); S::NUM_MEMORIES]: Sized,
 {
-  subtable_entries: [Vec<F>; S::NUM_SUBTABLES],
+  subtable_entries: Arc<[Vec<F>; S::NUM_SUBTABLES]>,
   pub lookup_polys: [DensePolynomial<F>; S::NUM_MEMORIES],
   pub combined_poly: DensePolynomial<F>,
   strategy: PhantomData<S>,
@@ -114,8 +153,17 @@ where
   /// Create new Subtables
   /// - `evaluations`: non-sparse evaluations of T[k] for each of the 'c'-dimensions as DensePolynomials
   pub fn new(nz: &[Vec<usize>; C], s: usize) -> Self {
+    Self::new_preprocessed(&SubtablePreprocessing::new(), nz, s)
+  }
+
+  /// Create new Subtables from subtables materialized ahead of time.
+  pub fn new_preprocessed(
+    preprocessing: &SubtablePreprocessing<F, C, M, S>,
+    nz: &[Vec<usize>; C],
+    s: usize,
+  ) -> Self {
     nz.iter().for_each(|nz_dim| assert_eq!(nz_dim.len(), s));
-    let subtable_entries = S::materialize_subtables();
+    let subtable_entries = Arc::clone(&preprocessing.subtable_entries);
     let lookup_polys: [DensePolynomial<F>; S::NUM_MEMORIES] =
       S::to_lookup_polys(&subtable_entries, nz, s);
     let combined_poly = DensePolynomial::merge(&lookup_polys);
@@ -227,12 +275,12 @@ pub struct CombinedTableEvalProof<G: CurveGroup, const C: usize> {
 }
 
 impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
-  fn prove_single(
+  fn prove_single<T: ProofTranscript<G>>(
     joint_poly: &DensePolynomial<G::ScalarField>,
     r: &[G::ScalarField],
     evals: Vec<G::ScalarField>,
     gens: &PolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
   ) -> PolyEvalProof<G> {
     assert_eq!(
@@ -241,11 +289,11 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     );
 
     // append the claimed evaluations to transcript
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"evals_ops_val", &evals);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"evals_ops_val", &evals);
 
     // n-to-1 reduction
     let (r_joint, eval_joint) = {
-      let challenges = <Transcript as ProofTranscript<G>>::challenge_vector(
+      let challenges = <T as ProofTranscript<G>>::challenge_vector(
         transcript,
         b"challenge_combine_n_to_one",
         evals.len().log_2() as usize,
@@ -264,7 +312,7 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
       (r_joint, joint_claim_eval)
     };
     // decommit the joint polynomial at r_joint
-    <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"joint_claim_eval", &eval_joint);
+    <T as ProofTranscript<G>>::append_scalar(transcript, b"joint_claim_eval", &eval_joint);
 
     let (proof_table_eval, _comm_table_eval) = PolyEvalProof::prove(
       joint_poly,
@@ -282,15 +330,15 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
 
   /// evalues both polynomials at r and produces a joint proof of opening
   #[tracing::instrument(skip_all, name = "CombinedEval.prove")]
-  pub fn prove(
+  pub fn prove<T: ProofTranscript<G>>(
     combined_poly: &DensePolynomial<G::ScalarField>,
     eval_ops_val_vec: &[G::ScalarField],
     r: &[G::ScalarField],
     gens: &PolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     random_tape: &mut RandomTape<G>,
   ) -> Self {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       CombinedTableEvalProof::<G, C>::protocol_name(),
     );
@@ -312,19 +360,19 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     CombinedTableEvalProof { proof_table_eval }
   }
 
-  fn verify_single(
+  fn verify_single<T: ProofTranscript<G>>(
     proof: &PolyEvalProof<G>,
     comm: &PolyCommitment<G>,
     r: &[G::ScalarField],
     evals: Vec<G::ScalarField>,
     gens: &PolyCommitmentGens<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> Result<(), ProofVerifyError> {
     // append the claimed evaluations to transcript
-    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"evals_ops_val", &evals);
+    <T as ProofTranscript<G>>::append_scalars(transcript, b"evals_ops_val", &evals);
 
     // n-to-1 reduction
-    let challenges = <Transcript as ProofTranscript<G>>::challenge_vector(
+    let challenges = <T as ProofTranscript<G>>::challenge_vector(
       transcript,
       b"challenge_combine_n_to_one",
       evals.len().log_2() as usize,
@@ -339,25 +387,21 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     r_joint.extend(r);
 
     // decommit the joint polynomial at r_joint
-    <Transcript as ProofTranscript<G>>::append_scalar(
-      transcript,
-      b"joint_claim_eval",
-      &joint_claim_eval,
-    );
+    <T as ProofTranscript<G>>::append_scalar(transcript, b"joint_claim_eval", &joint_claim_eval);
 
     proof.verify_plain(gens, transcript, &r_joint, &joint_claim_eval, comm)
   }
 
   // verify evaluations of both polynomials at r
-  pub fn verify(
+  pub fn verify<T: ProofTranscript<G>>(
     &self,
     r: &[G::ScalarField],
     evals: &[G::ScalarField],
     gens: &PolyCommitmentGens<G>,
     comm: &CombinedTableCommitment<G>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(
+    <T as ProofTranscript<G>>::append_protocol_name(
       transcript,
       CombinedTableEvalProof::<G, C>::protocol_name(),
     );
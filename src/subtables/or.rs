@@ -1,7 +1,7 @@
 use ark_ff::PrimeField;
 use ark_std::log2;
 
-use crate::utils::split_bits;
+use crate::utils::{instruction_utils::concatenate_lookups, split_bits};
 
 use super::SubtableStrategy;
 
@@ -45,13 +45,7 @@ impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
   /// T = T'[0] + 2^16*T'[1] + 2^32*T'[2] + 2^48*T'[3]
   /// T'[3] | T'[2] | T'[1] | T'[0]
   fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
-    let increment = log2(M) as usize / 2;
-    let mut sum = F::zero();
-    for (i, val) in vals.iter().enumerate() {
-      let weight: u64 = 1u64 << (i * increment);
-      sum += F::from(weight) * val;
-    }
-    sum
+    concatenate_lookups(vals, log2(M) as usize / 2)
   }
 
   fn g_poly_degree() -> usize {
@@ -62,7 +56,8 @@ impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
 #[cfg(test)]
 mod test {
   use crate::{
-    materialization_mle_parity_test, subtables::Subtables, utils::index_to_field_bitvector,
+    g_poly_degree_test, materialization_mle_parity_test, subtables::Subtables,
+    utils::index_to_field_bitvector,
   };
 
   use super::*;
@@ -146,4 +141,5 @@ mod test {
     16,
     2
   );
+  g_poly_degree_test!(g_poly_degree, OrSubtableStrategy, Fr, 4, 16);
 }
@@ -1,6 +1,8 @@
 use ark_ff::PrimeField;
 use ark_std::log2;
 
+use crate::utils::instruction_utils::concatenate_lookups;
+
 use super::SubtableStrategy;
 
 /// Used for lookups in the range [0, 2^LOG_R)
@@ -76,13 +78,7 @@ impl<F: PrimeField, const C: usize, const M: usize, const LOG_R: usize> Subtable
   /// T = T'[0] + 2^16*T'[1] + 2^32*T'[2] + 2^48*T'[3]
   /// T'[3] | T'[2] | T'[1] | T'[0]
   fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
-    let log_m = log2(M) as usize;
-    let mut sum = F::zero();
-    for (i, val) in vals.iter().enumerate() {
-      let weight: u64 = 1u64 << (i * log_m);
-      sum += F::from(weight) * val;
-    }
-    sum
+    concatenate_lookups(vals, log2(M) as usize)
   }
 
   fn g_poly_degree() -> usize {
@@ -92,7 +88,9 @@ impl<F: PrimeField, const C: usize, const M: usize, const LOG_R: usize> Subtable
 
 #[cfg(test)]
 mod test {
-  use crate::{materialization_mle_parity_test, utils::index_to_field_bitvector};
+  use crate::{
+    g_poly_degree_test, materialization_mle_parity_test, utils::index_to_field_bitvector,
+  };
 
   use super::*;
   use ark_curve25519::Fr;
@@ -127,6 +125,53 @@ mod test {
       .for_each(|&entry| assert_eq!(entry, Fr::zero()));
   }
 
+  type Strategy<const LOG_R: usize> = RangeCheckSubtableStrategy<LOG_R>;
+
+  /// Looks up `value` chunk by chunk (least significant chunk first) and combines the results.
+  fn lookup<const C: usize, const M: usize, const LOG_R: usize>(value: u64) -> Fr
+  where
+    [(); <Strategy<LOG_R> as SubtableStrategy<Fr, C, M>>::NUM_SUBTABLES]: Sized,
+    [(); <Strategy<LOG_R> as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES]: Sized,
+  {
+    let log_m = log2(M) as usize;
+    let subtables = <Strategy<LOG_R> as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    let vals: [Fr; <Strategy<LOG_R> as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES] =
+      std::array::from_fn(|i| {
+        let chunk = (value >> (i * log_m)) as usize % M;
+        let subtable_index =
+          <Strategy<LOG_R> as SubtableStrategy<Fr, C, M>>::memory_to_subtable_index(i);
+        subtables[subtable_index][chunk]
+      });
+    <Strategy<LOG_R> as SubtableStrategy<Fr, C, M>>::combine_lookups(&vals)
+  }
+
+  #[test]
+  fn in_range_values_reconstruct() {
+    // Every 16-bit value, checked against a 10-bit range: the third chunk only keeps 2 bits
+    for value in 0..(1 << 16) {
+      let in_range = value < (1 << 10);
+      assert_eq!(
+        lookup::<4, 16, 10>(value) == Fr::from(value),
+        in_range,
+        "value {value}"
+      );
+    }
+  }
+
+  #[test]
+  fn out_of_range_values_rejected() {
+    const LOG_R: usize = 40;
+    let max = (1u64 << LOG_R) - 1;
+    assert_eq!(lookup::<4, { 1 << 16 }, LOG_R>(0), Fr::zero());
+    assert_eq!(lookup::<4, { 1 << 16 }, LOG_R>(max), Fr::from(max));
+
+    // Any set bit at or above LOG_R changes the combined lookup
+    for bit in LOG_R..64 {
+      let value = max | (1 << bit);
+      assert_ne!(lookup::<4, { 1 << 16 }, LOG_R>(value), Fr::from(value));
+    }
+  }
+
   materialization_mle_parity_test!(
     materialization_parity,
     RangeCheckSubtableStrategy::<40>,
@@ -134,4 +179,11 @@ mod test {
     1 << 16,
     3
   );
+  g_poly_degree_test!(
+    g_poly_degree,
+    RangeCheckSubtableStrategy::<40>,
+    Fr,
+    3,
+    1 << 16
+  );
 }
@@ -0,0 +1,110 @@
+use ark_ff::PrimeField;
+use ark_std::log2;
+
+use super::SubtableStrategy;
+
+/// Sign extension of a `WIDTH`-bit value. Each entry is `2^WIDTH - 1` if bit `WIDTH - 1` of the
+/// index (the sign bit) is set and 0 otherwise, i.e. the bits to be filled in above a negative
+/// `WIDTH`-bit value. Index bits above the sign bit are ignored, so `WIDTH` must be at most
+/// `log2(M)`; with `WIDTH == log2(M)` the sign bit is the most significant bit of the chunk.
+///
+/// Only the least significant chunk (dimension 0) carries the value being extended.
+pub enum SignExtendSubtableStrategy<const WIDTH: usize> {}
+
+impl<F: PrimeField, const C: usize, const M: usize, const WIDTH: usize> SubtableStrategy<F, C, M>
+  for SignExtendSubtableStrategy<WIDTH>
+{
+  const NUM_SUBTABLES: usize = 1;
+  const NUM_MEMORIES: usize = C;
+
+  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
+    assert!(WIDTH > 0 && WIDTH <= log2(M) as usize);
+    let sign_bit = 1 << (WIDTH - 1);
+    let ones = F::from((1u64 << WIDTH) - 1);
+
+    let materialized: Vec<F> = (0..M)
+      .map(|idx| if idx & sign_bit != 0 { ones } else { F::zero() })
+      .collect();
+
+    [materialized]
+  }
+
+  /// SIGN_EXTEND = x_{sign} * (2^WIDTH - 1)
+  fn evaluate_subtable_mle(_: usize, point: &[F]) -> F {
+    debug_assert!(WIDTH <= point.len());
+    // Bits are ordered most significant first
+    let sign_bit = point[point.len() - WIDTH];
+    sign_bit * F::from((1u64 << WIDTH) - 1)
+  }
+
+  /// T = T'[0]
+  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
+    vals[0]
+  }
+
+  fn g_poly_degree() -> usize {
+    1
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+
+  use crate::{
+    g_poly_degree_test, materialization_mle_parity_test, utils::index_to_field_bitvector,
+  };
+
+  use super::*;
+
+  #[test]
+  fn table_materialization_hardcoded() {
+    const C: usize = 4;
+    const M: usize = 1 << 4;
+
+    let [full]: [Vec<Fr>; 1] =
+      <SignExtendSubtableStrategy<4> as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    assert_eq!(full.len(), M);
+    assert_eq!(full[0b0000], Fr::from(0));
+    assert_eq!(full[0b0111], Fr::from(0));
+    assert_eq!(full[0b1000], Fr::from(0b1111));
+    assert_eq!(full[0b1111], Fr::from(0b1111));
+
+    // Bits above the sign bit are ignored
+    let [narrow]: [Vec<Fr>; 1] =
+      <SignExtendSubtableStrategy<2> as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    assert_eq!(narrow[0b0001], Fr::from(0));
+    assert_eq!(narrow[0b0010], Fr::from(0b11));
+    assert_eq!(narrow[0b0100], Fr::from(0));
+    assert_eq!(narrow[0b1110], Fr::from(0b11));
+  }
+
+  #[test]
+  #[should_panic]
+  fn width_exceeds_chunk() {
+    let _ = <SignExtendSubtableStrategy<5> as SubtableStrategy<Fr, 4, 16>>::materialize_subtables();
+  }
+
+  materialization_mle_parity_test!(
+    materialization_parity,
+    SignExtendSubtableStrategy<4>,
+    Fr,
+    16,
+    1
+  );
+  materialization_mle_parity_test!(
+    materialization_parity_narrow,
+    SignExtendSubtableStrategy<3>,
+    Fr,
+    16,
+    1
+  );
+  materialization_mle_parity_test!(
+    materialization_parity_byte,
+    SignExtendSubtableStrategy<8>,
+    Fr,
+    1 << 16,
+    1
+  );
+  g_poly_degree_test!(g_poly_degree, SignExtendSubtableStrategy<8>, Fr, 1, 1 << 16);
+}
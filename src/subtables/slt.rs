@@ -0,0 +1,180 @@
+use ark_ff::PrimeField;
+use ark_std::log2;
+
+use crate::utils::{instruction_utils::combine_less_than, split_bits};
+
+use super::{eq::evaluate_eq_mle, SubtableStrategy};
+
+/// Signed (two's complement) less-than. The most significant chunk is looked up in a
+/// signed LT subtable which treats the top bit of each operand as its sign; all other
+/// chunks use the unsigned LT and EQ subtables.
+pub enum SLTSubtableStrategy {}
+
+impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
+  for SLTSubtableStrategy
+{
+  const NUM_SUBTABLES: usize = 3;
+  const NUM_MEMORIES: usize = 2 * C;
+
+  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
+    let bits_per_operand = (log2(M) / 2) as usize;
+    let sign_bit = 1 << (bits_per_operand - 1);
+
+    let mut materialized_lt: Vec<F> = Vec::with_capacity(M);
+    let mut materialized_eq: Vec<F> = Vec::with_capacity(M);
+    let mut materialized_slt: Vec<F> = Vec::with_capacity(M);
+
+    // Materialize table in counting order where lhs | rhs counts 0->m
+    for idx in 0..M {
+      let (lhs, rhs) = split_bits(idx, bits_per_operand);
+      materialized_lt.push(F::from(u64::from(lhs < rhs)));
+      materialized_eq.push(F::from(u64::from(lhs == rhs)));
+      // Flipping the sign bit maps two's complement order onto unsigned order
+      materialized_slt.push(F::from(u64::from((lhs ^ sign_bit) < (rhs ^ sign_bit))));
+    }
+
+    [materialized_lt, materialized_eq, materialized_slt]
+  }
+
+  /// LT = (1-x_i)* y_i * eq(x_{>i}, y_{>i})
+  /// SLT = x_0 * (1 - y_0) + eq(x_0, y_0) * LT(x_{>0}, y_{>0})
+  fn evaluate_subtable_mle(subtable_index: usize, point: &[F]) -> F {
+    debug_assert!(point.len() % 2 == 0);
+    let b = point.len() / 2;
+    let (x, y) = point.split_at(b);
+
+    match subtable_index {
+      0 => {
+        // LT subtable
+        let mut result = F::zero();
+        let mut eq_term = F::one();
+        for i in 0..b {
+          result += (F::one() - x[i]) * y[i] * eq_term;
+          eq_term *= F::one() - x[i] - y[i] + F::from(2u64) * x[i] * y[i];
+        }
+        result
+      }
+      1 => {
+        // EQ subtable
+        evaluate_eq_mle(point)
+      }
+      2 => {
+        // SLT subtable: x < y if x is negative and y is not, or if the signs match and
+        // the remaining bits compare as unsigned LT.
+        let mut result = x[0] * (F::one() - y[0]);
+        let mut eq_term = F::one() - x[0] - y[0] + F::from(2u64) * x[0] * y[0];
+        for i in 1..b {
+          result += (F::one() - x[i]) * y[i] * eq_term;
+          eq_term *= F::one() - x[i] - y[i] + F::from(2u64) * x[i] * y[i];
+        }
+        result
+      }
+      _ => panic!("SLTSubtableStrategy only has 3 subtables"),
+    }
+  }
+
+  /// Memory 0 is the most significant chunk and reads the SLT subtable;
+  /// the remaining memories alternate LT, EQ.
+  fn memory_to_subtable_index(memory_index: usize) -> usize {
+    assert!(memory_index < 2 * C);
+    if memory_index == 0 {
+      2
+    } else {
+      memory_index % 2
+    }
+  }
+
+  fn memory_to_dimension_index(memory_index: usize) -> usize {
+    assert!(memory_index < 2 * C);
+    memory_index / 2
+  }
+
+  /// Combines lookups into the SLT subtables.
+  /// Assumes `vals` are ordered: SLT[0], EQ[0], LT[1], EQ[1], ... LT[C-1], EQ[C-1]
+  /// T = SLT[0] + LT[1]*EQ[0] + ... + LT[C-1]*EQ[0]*...*EQ[C-2]
+  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
+    combine_less_than(vals)
+  }
+
+  fn g_poly_degree() -> usize {
+    C
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+
+  use crate::{
+    g_poly_degree_test, materialization_mle_parity_test, utils::index_to_field_bitvector,
+  };
+
+  use super::*;
+
+  #[test]
+  fn table_materialization_hardcoded() {
+    const C: usize = 2;
+    const M: usize = 16;
+    let materialized: [Vec<Fr>; 3] =
+      <SLTSubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    let slt = materialized[2].clone();
+
+    // 2-bit two's complement: 00 = 0, 01 = 1, 10 = -2, 11 = -1
+    assert_eq!(slt[0], Fr::from(0b00)); // 0 < 0 = false
+    assert_eq!(slt[1], Fr::from(0b01)); // 0 < 1 = true
+    assert_eq!(slt[2], Fr::from(0b00)); // 0 < -2 = false
+    assert_eq!(slt[3], Fr::from(0b00)); // 0 < -1 = false
+    assert_eq!(slt[8], Fr::from(0b01)); // -2 < 0 = true
+    assert_eq!(slt[11], Fr::from(0b01)); // -2 < -1 = true
+    assert_eq!(slt[14], Fr::from(0b00)); // -1 < -2 = false
+    assert_eq!(slt[15], Fr::from(0b00)); // -1 < -1 = false
+  }
+
+  #[test]
+  fn memory_mapping() {
+    const C: usize = 3;
+    const M: usize = 16;
+    let subtable_indices: Vec<usize> = (0..2 * C)
+      .map(<SLTSubtableStrategy as SubtableStrategy<Fr, C, M>>::memory_to_subtable_index)
+      .collect();
+    assert_eq!(subtable_indices, vec![2, 1, 0, 1, 0, 1]);
+  }
+
+  #[test]
+  fn signed_comparison() {
+    const C: usize = 2;
+    const M: usize = 16;
+    let [lt, eq, slt]: [Vec<Fr>; 3] =
+      <SLTSubtableStrategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+
+    // 4-bit operands split into two 2-bit chunks, most significant chunk first
+    for x in 0..16usize {
+      for y in 0..16usize {
+        let chunk = |shift: usize| (((x >> shift) & 0b11) << 2) | ((y >> shift) & 0b11);
+        let (hi, lo) = (chunk(2), chunk(0));
+        let vals: [Fr; C * 2] = [slt[hi], eq[hi], lt[lo], eq[lo]];
+        let combined = <SLTSubtableStrategy as SubtableStrategy<Fr, C, M>>::combine_lookups(&vals);
+
+        let sign_extend = |v: usize| ((v as i8) << 4) >> 4;
+        let expected = Fr::from(u64::from(sign_extend(x) < sign_extend(y)));
+        assert_eq!(combined, expected, "{x} < {y} (signed)");
+      }
+    }
+  }
+
+  materialization_mle_parity_test!(
+    slt_materialization_parity_test,
+    SLTSubtableStrategy,
+    Fr,
+    /* m = */ 16,
+    /* NUM_SUBTABLES = */ 3
+  );
+  materialization_mle_parity_test!(
+    slt_materialization_parity_test_big_m,
+    SLTSubtableStrategy,
+    Fr,
+    /* m = */ 1 << 8,
+    /* NUM_SUBTABLES = */ 3
+  );
+  g_poly_degree_test!(g_poly_degree, SLTSubtableStrategy, Fr, 4, 16);
+}
@@ -34,7 +34,59 @@ macro_rules! materialization_mle_parity_test {
                     "Subtable {subtable_index} index {input_index} did not match between MLE and materialized subtable."
                 );
             }
+
+            // Off the hypercube, the MLE must agree with the multilinear extension of the table
+            let r: Vec<$F> = $crate::utils::test::gen_random_point(operand_bits);
+            assert_eq!(
+                $crate::poly::dense_mlpoly::DensePolynomial::new(materialized_table.clone()).evaluate(&r),
+                <$table_type as SubtableStrategy<$F, C, M>>::evaluate_subtable_mle(subtable_index, &r),
+                "Subtable {subtable_index} did not match between MLE and materialized subtable at a random point."
+            );
         }
     }
     };
 }
+
+/// Checks that `g_poly_degree` is the total degree of `combine_lookups`. Restricted to a random
+/// line through the space of subtable outputs, `combine_lookups` is a univariate polynomial of
+/// the same degree (with high probability), which is interpolated from `g_poly_degree() + 1`
+/// points and checked at random points off the interpolation set.
+#[macro_export]
+macro_rules! g_poly_degree_test {
+  ($test_name:ident, $table_type:ty, $F:ty, $C:expr, $M:expr) => {
+    #[test]
+    fn $test_name() {
+      use ark_std::{test_rng, UniformRand, Zero};
+      use $crate::poly::unipoly::UniPoly;
+
+      const C: usize = $C;
+      const M: usize = $M;
+      const NUM_MEMORIES: usize = <$table_type as SubtableStrategy<$F, C, M>>::NUM_MEMORIES;
+
+      let mut rng = test_rng();
+      let a: [$F; NUM_MEMORIES] = std::array::from_fn(|_| <$F>::rand(&mut rng));
+      let b: [$F; NUM_MEMORIES] = std::array::from_fn(|_| <$F>::rand(&mut rng));
+      let g = |t: $F| {
+        let vals: [$F; NUM_MEMORIES] = std::array::from_fn(|i| a[i] + t * b[i]);
+        <$table_type as SubtableStrategy<$F, C, M>>::combine_lookups(&vals)
+      };
+
+      let degree = <$table_type as SubtableStrategy<$F, C, M>>::g_poly_degree();
+      let evals: Vec<$F> = (0..=degree).map(|t| g(<$F>::from(t as u64))).collect();
+      let poly = UniPoly::from_evals(&evals);
+
+      assert!(
+        !poly.as_vec()[degree].is_zero(),
+        "combine_lookups has degree less than g_poly_degree() = {degree}"
+      );
+      for _ in 0..4 {
+        let t = <$F>::rand(&mut rng);
+        assert_eq!(
+          poly.evaluate(&t),
+          g(t),
+          "combine_lookups has degree greater than g_poly_degree() = {degree}"
+        );
+      }
+    }
+  };
+}
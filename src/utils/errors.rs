@@ -11,6 +11,18 @@ pub enum ProofVerifyError {
   InternalError,
   #[error("Compressed group element failed to decompress: {0:?}")]
   DecompressionError([u8; 32]),
+  #[error(
+    "Multiset equality check failed for memory {memory}: (init, read, write, final) hashes {hashes}"
+  )]
+  MultisetMismatch { memory: usize, hashes: String },
+  #[error("Reed-Solomon fingerprint does not match the grand product claim")]
+  FingerprintMismatch,
+  #[error("Polynomial opening failed to verify")]
+  OpeningFailed,
+  #[error("Invalid proof shape")]
+  InvalidShape,
+  #[error("Sumcheck or grand product claim does not match the prover's messages")]
+  ClaimMismatch,
 }
 
 impl Default for ProofVerifyError {
@@ -0,0 +1,134 @@
+use ark_ff::PrimeField;
+
+/// Folds per-chunk LT and EQ signals from the most to least significant chunk into x < y.
+/// Assumes `vals` are ordered: LT[0], EQ[0], ... LT[C-1], EQ[C-1], with chunk 0 the most significant.
+/// T = LT[0] + LT[1]*EQ[0] + ... + LT[C-1]*EQ[0]*...*EQ[C-2]
+pub fn combine_less_than<F: PrimeField>(vals: &[F]) -> F {
+  debug_assert!(vals.len() % 2 == 0);
+  let mut sum = F::zero();
+  let mut eq_prod = F::one();
+
+  for chunk in vals.chunks_exact(2) {
+    sum += chunk[0] * eq_prod;
+    eq_prod *= chunk[1];
+  }
+  sum
+}
+
+/// x <= y, i.e. x < y or every chunk is equal.
+pub fn combine_less_than_or_equal<F: PrimeField>(vals: &[F]) -> F {
+  let eq_prod: F = vals.iter().skip(1).step_by(2).product();
+  combine_less_than(vals) + eq_prod
+}
+
+/// Concatenates chunk values of `bits` bits each, with chunk 0 the least significant.
+/// T = vals[0] + 2^bits * vals[1] + ... + 2^{(C-1) * bits} * vals[C-1]
+pub fn concatenate_lookups<F: PrimeField>(vals: &[F], bits: usize) -> F {
+  concatenate_lookups_variable(vals, &vec![bits; vals.len()])
+}
+
+/// Like `concatenate_lookups`, but chunk `i` contributes `bits_per_chunk[i]` bits, so each value is
+/// shifted by the total width of the chunks below it.
+pub fn concatenate_lookups_variable<F: PrimeField>(vals: &[F], bits_per_chunk: &[usize]) -> F {
+  assert_eq!(vals.len(), bits_per_chunk.len());
+  let mut sum = F::zero();
+  let mut weight = F::one();
+  for (val, bits) in vals.iter().zip(bits_per_chunk.iter()) {
+    sum += weight * val;
+    for _ in 0..*bits {
+      weight.double_in_place();
+    }
+  }
+  sum
+}
+
+/// Panics in debug builds if any chunk of a lookup index falls outside a subtable of size `m`, as
+/// happens when `C` and `log_m` do not match the operand width.
+pub fn debug_assert_indices_in_range(indices: &[usize], m: usize) {
+  for (i, index) in indices.iter().enumerate() {
+    debug_assert!(
+      *index < m,
+      "lookup index chunk {i} is {index}, out of range for subtable of size {m}"
+    );
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::Fr;
+
+  use super::*;
+
+  #[test]
+  fn combine_less_than_three_chunks() {
+    // 6-bit operands split into three 2-bit chunks, most significant chunk first
+    for x in 0..64u64 {
+      for y in 0..64u64 {
+        let vals: Vec<Fr> = (0..3)
+          .rev()
+          .flat_map(|i| {
+            let (x_i, y_i) = ((x >> (2 * i)) & 0b11, (y >> (2 * i)) & 0b11);
+            [
+              Fr::from(u64::from(x_i < y_i)),
+              Fr::from(u64::from(x_i == y_i)),
+            ]
+          })
+          .collect();
+
+        assert_eq!(
+          combine_less_than(&vals),
+          Fr::from(u64::from(x < y)),
+          "{x} < {y}"
+        );
+        assert_eq!(
+          combine_less_than_or_equal(&vals),
+          Fr::from(u64::from(x <= y)),
+          "{x} <= {y}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn concatenate_lookups_widths() {
+    let x: u64 = 0xDEAD_BEEF;
+    let chunks = |bits_per_chunk: &[usize]| -> Vec<Fr> {
+      let mut shift = 0;
+      bits_per_chunk
+        .iter()
+        .map(|bits| {
+          let chunk = (x >> shift) & ((1 << bits) - 1);
+          shift += bits;
+          Fr::from(chunk)
+        })
+        .collect()
+    };
+
+    for widths in [vec![8, 8, 8, 8], vec![16, 16], vec![4, 12, 16]] {
+      assert_eq!(
+        concatenate_lookups_variable(&chunks(&widths), &widths),
+        Fr::from(x)
+      );
+    }
+    assert_eq!(concatenate_lookups(&chunks(&[8; 4]), 8), Fr::from(x));
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "lookup index chunk 1 is 16")]
+  fn indices_out_of_range() {
+    debug_assert_indices_in_range(&[3, 16, 2], 16);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "out of range for subtable of size 16")]
+  fn mismatched_chunking_caught() {
+    use crate::{lasso::densified::DensifiedRepresentation, utils::test::chunk_operands};
+
+    // 32-bit operands need C = 4 chunks of 2 * 4 bits at log_m = 8; with C = 4 and log_m = 4 the
+    // operands are chunked as if log_m were 8 and overflow the 16-entry subtables.
+    let indices: [usize; 4] = chunk_operands(0xFFFF_FFFF, 0xFFFF_FFFF, 8);
+    let _ = DensifiedRepresentation::<Fr, 4>::from_lookup_indices(&vec![indices], 4);
+  }
+}
@@ -10,7 +10,10 @@ pub mod test;
 
 pub mod errors;
 pub mod gaussian_elimination;
+pub mod instruction_utils;
 pub mod math;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod random;
 pub mod transcript;
 
@@ -60,6 +63,19 @@ pub fn ff_bitvector_dbg<F: PrimeField>(f: &Vec<F>) -> String {
   result
 }
 
+/// Turns `$e` into a rayon parallel iterator when the `multicore` feature is enabled and into a
+/// serial iterator otherwise, so call sites can share a single `map`/`collect` chain.
+macro_rules! maybe_par_iter {
+  ($e:expr) => {{
+    #[cfg(feature = "multicore")]
+    let iter = rayon::iter::IntoParallelIterator::into_par_iter($e);
+    #[cfg(not(feature = "multicore"))]
+    let iter = ::core::iter::IntoIterator::into_iter($e);
+    iter
+  }};
+}
+pub(crate) use maybe_par_iter;
+
 #[tracing::instrument(skip_all, name = "compute_dotproduct")]
 pub fn compute_dotproduct<F: PrimeField>(a: &[F], b: &[F]) -> F {
   assert_eq!(a.len(), b.len());
@@ -72,6 +88,44 @@ pub fn compute_dotproduct<F: PrimeField>(a: &[F], b: &[F]) -> F {
   dot_product
 }
 
+/// Inverts every element of `v` in place with Montgomery's trick: one field inversion plus
+/// 3(n - 1) multiplications. Zero elements are left as zero.
+pub fn batch_invert<F: PrimeField>(v: &mut [F]) {
+  // prefix[i] = product of the nonzero elements of v[..i]
+  let mut prefix = Vec::with_capacity(v.len());
+  let mut acc = F::one();
+  for x in v.iter() {
+    prefix.push(acc);
+    if !x.is_zero() {
+      acc *= x;
+    }
+  }
+
+  let mut acc_inv = match acc.inverse() {
+    Some(inv) => inv,
+    None => return,
+  };
+  for (x, prefix) in v.iter_mut().zip(prefix).rev() {
+    if x.is_zero() {
+      continue;
+    }
+    let x_inv = acc_inv * prefix;
+    acc_inv *= *x;
+    *x = x_inv;
+  }
+}
+
+/// Final timestamps of a read-only memory of `memory_size` cells after the accesses in
+/// `access_sequence`: each access increments its cell's timestamp, so this is the number of times
+/// each address was accessed (0 for addresses never accessed).
+pub fn compute_final_timestamps(access_sequence: &[usize], memory_size: usize) -> Vec<usize> {
+  let mut final_timestamps = vec![0usize; memory_size];
+  for &address in access_sequence {
+    final_timestamps[address] += 1;
+  }
+  final_timestamps
+}
+
 /// Checks if `num` is a power of 2.
 pub fn is_power_of_two(num: usize) -> bool {
   num != 0 && (num & (num - 1)) == 0
@@ -92,6 +146,39 @@ pub fn split_bits(item: usize, num_bits: usize) -> (usize, usize) {
 mod tests {
   use super::*;
 
+  #[test]
+  fn batch_invert_matches_inverse() {
+    use ark_curve25519::Fr;
+    use ark_ff::Field;
+    use ark_std::{test_rng, UniformRand, Zero};
+
+    let mut rng = test_rng();
+    let mut v: Vec<Fr> = (0..64).map(|_| Fr::rand(&mut rng)).collect();
+    v[0] = Fr::zero();
+    v[17] = Fr::zero();
+    let expected: Vec<Fr> = v
+      .iter()
+      .map(|x| x.inverse().unwrap_or_else(Fr::zero))
+      .collect();
+
+    batch_invert(&mut v);
+    assert_eq!(v, expected);
+
+    let mut empty: Vec<Fr> = vec![];
+    batch_invert(&mut empty);
+  }
+
+  #[test]
+  fn final_timestamps() {
+    // Access sequence and final timestamps from the memory checking test fixture
+    assert_eq!(
+      compute_final_timestamps(&[1, 2, 1, 5], 8),
+      vec![0, 2, 1, 0, 0, 1, 0, 0]
+    );
+    assert_eq!(compute_final_timestamps(&[], 4), vec![0; 4]);
+    assert_eq!(compute_final_timestamps(&[3, 3, 3], 4), vec![0, 0, 0, 3]);
+  }
+
   #[test]
   fn split() {
     assert_eq!(split_bits(0b00_01, 2), (0, 1));
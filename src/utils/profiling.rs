@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, Registry};
+
+/// Total wall-clock time and number of closed spans, per span name.
+#[derive(Debug, Default, Clone)]
+pub struct SpanSummary {
+  totals: HashMap<&'static str, (Duration, usize)>,
+}
+
+impl SpanSummary {
+  /// `(name, total time, count)` for every span name recorded, slowest first.
+  pub fn rows(&self) -> Vec<(&'static str, Duration, usize)> {
+    let mut rows: Vec<_> = self
+      .totals
+      .iter()
+      .map(|(name, (time, count))| (*name, *time, *count))
+      .collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    rows
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.totals.is_empty()
+  }
+
+  /// Total wall-clock time spent in spans named `name`, if any were recorded.
+  pub fn total(&self, name: &str) -> Option<Duration> {
+    self.totals.get(name).map(|(time, _)| *time)
+  }
+}
+
+impl fmt::Display for SpanSummary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let rows = self.rows();
+    let width = rows
+      .iter()
+      .map(|(name, _, _)| name.len())
+      .max()
+      .unwrap_or(0)
+      .max(4);
+    writeln!(f, "{:<width$} {:>14} {:>8}", "span", "total", "count")?;
+    for (name, time, count) in rows {
+      writeln!(f, "{name:<width$} {:>14} {count:>8}", format!("{time:.3?}"))?;
+    }
+    Ok(())
+  }
+}
+
+/// Creation time of a span, stored in its extensions.
+struct SpanStart(Instant);
+
+/// Layer accumulating the wall-clock time between creation and close of every span.
+struct SummaryLayer {
+  totals: Arc<Mutex<HashMap<&'static str, (Duration, usize)>>>,
+}
+
+impl<S> Layer<S> for SummaryLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+    if let Some(span) = ctx.span(id) {
+      span.extensions_mut().insert(SpanStart(Instant::now()));
+    }
+  }
+
+  fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+    if let Some(span) = ctx.span(&id) {
+      let extensions = span.extensions();
+      if let Some(SpanStart(start)) = extensions.get::<SpanStart>() {
+        let mut totals = self.totals.lock().unwrap();
+        let (time, count) = totals.entry(span.name()).or_default();
+        *time += start.elapsed();
+        *count += 1;
+      }
+    }
+  }
+}
+
+/// Runs `f` with a subscriber recording per-span wall-clock totals, prints them as a table sorted
+/// by time and returns them alongside the result of `f`.
+///
+/// The subscriber is only the default for the current thread: spans created on rayon worker
+/// threads are not recorded, but their time is included in the enclosing span on this thread.
+pub fn summarize<R>(f: impl FnOnce() -> R) -> (R, SpanSummary) {
+  let totals = Arc::new(Mutex::new(HashMap::new()));
+  let subscriber = Registry::default().with(SummaryLayer {
+    totals: totals.clone(),
+  });
+  let result = tracing::subscriber::with_default(subscriber, f);
+
+  let summary = SpanSummary {
+    totals: totals.lock().unwrap().clone(),
+  };
+  println!("{summary}");
+  (result, summary)
+}
+
+#[cfg(test)]
+mod tests {
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+  use merlin::Transcript;
+
+  use super::*;
+  use crate::lasso::densified::DensifiedRepresentation;
+  use crate::lasso::surge::{SparsePolyCommitmentGens, SparsePolynomialEvaluationProof};
+  use crate::subtables::and::AndSubtableStrategy;
+  use crate::subtables::SubtableStrategy;
+  use crate::utils::math::Math;
+  use crate::utils::random::RandomTape;
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  #[test]
+  fn summarize_proof() {
+    const C: usize = 2;
+    const M: usize = 16;
+    const S: usize = 16;
+    const NUM_MEMORIES: usize = <AndSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+
+    let ((), summary) = summarize(|| {
+      let mut dense: DensifiedRepresentation<Fr, C> =
+        DensifiedRepresentation::from_lookup_indices(&gen_indices(S, M), M.log_2());
+      let gens = SparsePolyCommitmentGens::<G1Projective>::new(
+        b"gens_sparse_poly",
+        C,
+        S,
+        NUM_MEMORIES,
+        M.log_2(),
+      );
+      let commitment = dense.commit::<G1Projective>(&gens);
+      let r: Vec<Fr> = gen_random_point(S.log_2());
+
+      let _proof =
+        SparsePolynomialEvaluationProof::<G1Projective, C, M, AndSubtableStrategy>::prove(
+          &mut dense,
+          &commitment,
+          &r,
+          &gens,
+          &mut Transcript::new(b"example"),
+          &mut RandomTape::new(b"proof"),
+        )
+        .unwrap();
+    });
+
+    assert!(!summary.is_empty());
+    assert!(summary.total("SparsePoly.prove").is_some());
+    assert!(summary.total("BatchedGrandProductArgument.prove").is_some());
+    assert!(summary.to_string().contains("ProductLayer.prove"));
+  }
+}
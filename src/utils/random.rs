@@ -29,6 +29,18 @@ impl<G: CurveGroup> RandomTape<G> {
     }
   }
 
+  /// Tape whose randomness is fully determined by `seed`, so proofs built with the same seed are
+  /// byte-identical. Intended for tests and golden files only: blinds derived from a known seed
+  /// provide no hiding and must not be used in production.
+  pub fn new_deterministic(seed: u64) -> Self {
+    let mut tape = Transcript::new(b"deterministic_tape");
+    tape.append_u64(b"init_seed", seed);
+    Self {
+      tape,
+      phantom: PhantomData,
+    }
+  }
+
   pub fn random_scalar(&mut self, label: &'static [u8]) -> G::ScalarField {
     <Transcript as ProofTranscript<G>>::challenge_scalar(&mut self.tape, label)
   }
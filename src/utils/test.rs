@@ -31,6 +31,43 @@ pub fn gen_indices<const C: usize>(sparsity: usize, memory_size: usize) -> Vec<[
   all_indices
 }
 
+/// Random (init, read, write, final) grand product hashes. If `consistent`, they satisfy the
+/// memory-checking relation init * write == read * final; otherwise they are guaranteed not to.
+pub fn gen_multiset_hashes<F: PrimeField>(
+  rng: &mut impl RngCore,
+  consistent: bool,
+) -> (F, F, F, F) {
+  let hash_init: F = nonzero(rng);
+  let hash_read = nonzero(rng);
+  let hash_final = nonzero(rng);
+  let mut hash_write = hash_read * hash_final * hash_init.inverse().unwrap();
+  if !consistent {
+    hash_write += nonzero::<F>(rng);
+  }
+  (hash_init, hash_read, hash_write, hash_final)
+}
+
+fn nonzero<F: PrimeField>(rng: &mut impl RngCore) -> F {
+  loop {
+    let x = F::rand(rng);
+    if !x.is_zero() {
+      return x;
+    }
+  }
+}
+
+/// Splits operands `x` and `y` into `C` chunks of `log_m / 2` bits, least significant chunk first,
+/// and concatenates each pair of chunks into a subtable index `x_i | y_i`.
+pub fn chunk_operands<const C: usize>(x: u64, y: u64, log_m: usize) -> [usize; C] {
+  let operand_bits = log_m / 2;
+  let mask = (1u64 << operand_bits) - 1;
+  std::array::from_fn(|i| {
+    let x_i = (x >> (i * operand_bits)) & mask;
+    let y_i = (y >> (i * operand_bits)) & mask;
+    ((x_i << operand_bits) | y_i) as usize
+  })
+}
+
 /// Wrapper around merlin_transcript that allows overriding
 pub struct TestTranscript<F> {
   pub merlin_transcript: Transcript,
@@ -14,6 +14,8 @@ pub trait ProofTranscript<G: CurveGroup> {
   fn append_point(&mut self, label: &'static [u8], point: &G);
   fn append_points(&mut self, label: &'static [u8], points: &[G]);
   fn challenge_scalar(&mut self, label: &'static [u8]) -> G::ScalarField;
+  /// Squeezes `len` challenges under a single label. Derived exactly as `len` sequential
+  /// `challenge_scalar(label)` calls, so call sites may use either form interchangeably.
   fn challenge_vector(&mut self, label: &'static [u8], len: usize) -> Vec<G::ScalarField>;
 }
 
@@ -71,6 +73,310 @@ impl<G: CurveGroup> ProofTranscript<G> for Transcript {
   }
 }
 
+/// Fiat-Shamir transcript backed by Blake3 rather than Merlin's Strobe/Keccak construction.
+/// Each message is absorbed by hashing it, length-prefixed, together with the current state;
+/// challenges are squeezed from the XOF output and then ratcheted back into the state.
+#[cfg(feature = "blake3")]
+#[derive(Clone)]
+pub struct Blake3Transcript {
+  state: [u8; 32],
+}
+
+#[cfg(feature = "blake3")]
+impl Blake3Transcript {
+  pub fn new(label: &'static [u8]) -> Self {
+    let mut transcript = Self { state: [0u8; 32] };
+    transcript.absorb(b"dom-sep", label);
+    transcript
+  }
+
+  fn absorb(&mut self, label: &[u8], msg: &[u8]) {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&self.state);
+    hasher.update(&(label.len() as u64).to_le_bytes());
+    hasher.update(label);
+    hasher.update(&(msg.len() as u64).to_le_bytes());
+    hasher.update(msg);
+    self.state = *hasher.finalize().as_bytes();
+  }
+
+  pub fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+    self.absorb(label, &(dest.len() as u64).to_le_bytes());
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&self.state);
+    hasher.finalize_xof().fill(dest);
+    self.absorb(b"challenge", dest);
+  }
+}
+
+#[cfg(feature = "blake3")]
+impl<G: CurveGroup> ProofTranscript<G> for Blake3Transcript {
+  fn append_message(&mut self, label: &'static [u8], msg: &'static [u8]) {
+    self.absorb(label, msg);
+  }
+
+  fn append_u64(&mut self, label: &'static [u8], x: u64) {
+    self.absorb(label, &x.to_le_bytes());
+  }
+
+  fn append_protocol_name(&mut self, protocol_name: &'static [u8]) {
+    self.absorb(b"protocol-name", protocol_name);
+  }
+
+  fn append_scalar(&mut self, label: &'static [u8], scalar: &G::ScalarField) {
+    let mut buf = vec![];
+    scalar.serialize_compressed(&mut buf).unwrap();
+    self.absorb(label, &buf);
+  }
+
+  fn append_scalars(&mut self, label: &'static [u8], scalars: &[G::ScalarField]) {
+    self.absorb(label, b"begin_append_vector");
+    for item in scalars.iter() {
+      <Self as ProofTranscript<G>>::append_scalar(self, label, item);
+    }
+    self.absorb(label, b"end_append_vector");
+  }
+
+  fn append_point(&mut self, label: &'static [u8], point: &G) {
+    let mut buf = vec![];
+    point.serialize_compressed(&mut buf).unwrap();
+    self.absorb(label, &buf);
+  }
+
+  fn append_points(&mut self, label: &'static [u8], points: &[G]) {
+    self.absorb(label, b"begin_append_vector");
+    for item in points.iter() {
+      self.append_point(label, item);
+    }
+    self.absorb(label, b"end_append_vector");
+  }
+
+  fn challenge_scalar(&mut self, label: &'static [u8]) -> G::ScalarField {
+    let mut buf = [0u8; 64];
+    self.challenge_bytes(label, &mut buf);
+    G::ScalarField::from_le_bytes_mod_order(&buf)
+  }
+
+  fn challenge_vector(&mut self, label: &'static [u8], len: usize) -> Vec<G::ScalarField> {
+    (0..len)
+      .map(|_i| <Self as ProofTranscript<G>>::challenge_scalar(self, label))
+      .collect::<Vec<G::ScalarField>>()
+  }
+}
+
+/// A single call made against a [`RecordingTranscript`]: the label and the bytes absorbed, or the
+/// label and the bytes of the challenge that was squeezed.
+#[cfg(feature = "transcript-debug")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TranscriptEvent {
+  Append {
+    label: &'static [u8],
+    bytes: Vec<u8>,
+  },
+  Challenge {
+    label: &'static [u8],
+    bytes: Vec<u8>,
+  },
+}
+
+#[cfg(feature = "transcript-debug")]
+impl TranscriptEvent {
+  pub fn label(&self) -> &'static [u8] {
+    match self {
+      TranscriptEvent::Append { label, .. } | TranscriptEvent::Challenge { label, .. } => label,
+    }
+  }
+}
+
+/// Wraps a transcript and records every append and challenge made against it, so that the
+/// prover's and verifier's logs can be diffed to find the first Fiat-Shamir divergence.
+#[cfg(feature = "transcript-debug")]
+pub struct RecordingTranscript<T> {
+  inner: T,
+  events: Vec<TranscriptEvent>,
+}
+
+#[cfg(feature = "transcript-debug")]
+impl<T> RecordingTranscript<T> {
+  pub fn new(inner: T) -> Self {
+    Self {
+      inner,
+      events: Vec::new(),
+    }
+  }
+
+  pub fn events(&self) -> &[TranscriptEvent] {
+    &self.events
+  }
+
+  pub fn into_events(self) -> Vec<TranscriptEvent> {
+    self.events
+  }
+
+  fn record_append(&mut self, label: &'static [u8], bytes: Vec<u8>) {
+    self.events.push(TranscriptEvent::Append { label, bytes });
+  }
+
+  fn record_challenge<F: CanonicalSerialize>(&mut self, label: &'static [u8], challenges: &[F]) {
+    let mut bytes = vec![];
+    challenges.serialize_compressed(&mut bytes).unwrap();
+    self
+      .events
+      .push(TranscriptEvent::Challenge { label, bytes });
+  }
+}
+
+/// Index of the first event at which two transcript logs differ, if any. A log that is a strict
+/// prefix of the other diverges at its length.
+#[cfg(feature = "transcript-debug")]
+pub fn first_divergence(a: &[TranscriptEvent], b: &[TranscriptEvent]) -> Option<usize> {
+  a.iter()
+    .zip(b.iter())
+    .position(|(x, y)| x != y)
+    .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
+
+#[cfg(feature = "transcript-debug")]
+impl<G: CurveGroup, T: ProofTranscript<G>> ProofTranscript<G> for RecordingTranscript<T> {
+  fn append_message(&mut self, label: &'static [u8], msg: &'static [u8]) {
+    self.record_append(label, msg.to_vec());
+    self.inner.append_message(label, msg);
+  }
+
+  fn append_u64(&mut self, label: &'static [u8], x: u64) {
+    self.record_append(label, x.to_le_bytes().to_vec());
+    self.inner.append_u64(label, x);
+  }
+
+  fn append_protocol_name(&mut self, protocol_name: &'static [u8]) {
+    self.record_append(b"protocol-name", protocol_name.to_vec());
+    self.inner.append_protocol_name(protocol_name);
+  }
+
+  fn append_scalar(&mut self, label: &'static [u8], scalar: &G::ScalarField) {
+    let mut buf = vec![];
+    scalar.serialize_compressed(&mut buf).unwrap();
+    self.record_append(label, buf);
+    self.inner.append_scalar(label, scalar);
+  }
+
+  fn append_scalars(&mut self, label: &'static [u8], scalars: &[G::ScalarField]) {
+    let mut buf = vec![];
+    scalars.serialize_compressed(&mut buf).unwrap();
+    self.record_append(label, buf);
+    self.inner.append_scalars(label, scalars);
+  }
+
+  fn append_point(&mut self, label: &'static [u8], point: &G) {
+    let mut buf = vec![];
+    point.serialize_compressed(&mut buf).unwrap();
+    self.record_append(label, buf);
+    self.inner.append_point(label, point);
+  }
+
+  fn append_points(&mut self, label: &'static [u8], points: &[G]) {
+    let mut buf = vec![];
+    points.serialize_compressed(&mut buf).unwrap();
+    self.record_append(label, buf);
+    self.inner.append_points(label, points);
+  }
+
+  fn challenge_scalar(&mut self, label: &'static [u8]) -> G::ScalarField {
+    let challenge = self.inner.challenge_scalar(label);
+    self.record_challenge(label, &[challenge]);
+    challenge
+  }
+
+  fn challenge_vector(&mut self, label: &'static [u8], len: usize) -> Vec<G::ScalarField> {
+    let challenges = self.inner.challenge_vector(label, len);
+    self.record_challenge(label, &challenges);
+    challenges
+  }
+}
+
 pub trait AppendToTranscript<G: CurveGroup> {
   fn append_to_transcript<T: ProofTranscript<G>>(&self, label: &'static [u8], transcript: &mut T);
 }
+
+#[cfg(test)]
+mod test {
+  use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+
+  use super::*;
+
+  fn challenge_vector_matches_sequential<T: ProofTranscript<G1Projective>>(
+    new_transcript: impl Fn() -> T,
+  ) {
+    let mut batched = new_transcript();
+    let mut sequential = new_transcript();
+    <T as ProofTranscript<G1Projective>>::append_scalar(&mut batched, b"x", &Fr::from(7u64));
+    <T as ProofTranscript<G1Projective>>::append_scalar(&mut sequential, b"x", &Fr::from(7u64));
+
+    let vector = <T as ProofTranscript<G1Projective>>::challenge_vector(&mut batched, b"r", 4);
+    let scalars: Vec<Fr> = (0..4)
+      .map(|_| <T as ProofTranscript<G1Projective>>::challenge_scalar(&mut sequential, b"r"))
+      .collect();
+    assert_eq!(vector, scalars);
+
+    // Transcripts are left in the same state
+    assert_eq!(
+      <T as ProofTranscript<G1Projective>>::challenge_scalar(&mut batched, b"next"),
+      <T as ProofTranscript<G1Projective>>::challenge_scalar(&mut sequential, b"next"),
+    );
+  }
+
+  #[test]
+  fn challenge_vector_sequential_merlin() {
+    challenge_vector_matches_sequential(|| Transcript::new(b"test"));
+  }
+
+  #[cfg(feature = "blake3")]
+  #[test]
+  fn challenge_vector_sequential_blake3() {
+    challenge_vector_matches_sequential(|| Blake3Transcript::new(b"test"));
+  }
+
+  #[cfg(feature = "transcript-debug")]
+  #[test]
+  fn recorded_prove_verify_logs() {
+    use crate::poly::dense_mlpoly::DensePolynomial;
+    use crate::subprotocols::sumcheck::SumcheckInstanceProof;
+    use ark_std::One;
+
+    let num_vars = 3;
+    let poly = DensePolynomial::new((0..1 << num_vars).map(|i| Fr::from(i as u64)).collect());
+    let claim: Fr = (0..1 << num_vars)
+      .map(|i| poly[i] * poly[i] * poly[i])
+      .sum();
+    let comb_func = |vals: &[Fr; 3]| -> Fr { vals[0] * vals[1] * vals[2] };
+
+    let mut prover_transcript = RecordingTranscript::new(Transcript::new(b"test"));
+    let (proof, _, _) = SumcheckInstanceProof::<Fr>::prove_arbitrary::<_, G1Projective, _, 3>(
+      &claim,
+      num_vars,
+      &mut [poly.clone(), poly.clone(), poly],
+      comb_func,
+      3,
+      &mut prover_transcript,
+    );
+    let prover_events = prover_transcript.into_events();
+
+    let verify = |claim: Fr| {
+      let mut verifier_transcript = RecordingTranscript::new(Transcript::new(b"test"));
+      proof
+        .verify::<G1Projective, _>(claim, num_vars, 3, &mut verifier_transcript)
+        .unwrap();
+      verifier_transcript.into_events()
+    };
+
+    let verifier_events = verify(claim);
+    assert_eq!(first_divergence(&prover_events, &verifier_events), None);
+
+    // A wrong claim changes the linear coefficient the verifier recovers for the first round
+    // polynomial: "UniPoly_begin" and the constant coefficient still match.
+    let verifier_events = verify(claim + Fr::one());
+    let index = first_divergence(&prover_events, &verifier_events).unwrap();
+    assert_eq!(index, 2);
+    assert_eq!(prover_events[index].label(), b"coeff");
+  }
+}